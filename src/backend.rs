@@ -0,0 +1,482 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::{StatusCode, Url};
+use serde::Deserialize;
+use std::fmt::Debug;
+
+use crate::DomainResult;
+
+const AVAIL_API_URL: &str = "https://registro.br/v2/ajax/avail/raw/";
+const RDAP_API_URL: &str = "https://rdap.registro.br/domain/";
+
+/// Fonte das respostas de disponibilidade. O [`Checker`](crate::Checker) cuida das
+/// requisições, novas tentativas e rate limiting; o backend apenas monta a URL e
+/// interpreta a resposta, sempre no mesmo formato de [`DomainResult`].
+pub trait Backend: Debug + Send + Sync {
+    /// URL consultada para o FQDN `domain`
+    fn url(&self, domain: &str) -> String;
+
+    /// Interpreta uma resposta que não foi de rate limiting (429)
+    fn interpret(&self, domain: String, status: StatusCode, body: &str) -> DomainResult;
+}
+
+/// Resposta da API de disponibilidade do Registro.br
+/// status: 0 = disponível, 2 = registrado, 3 = em processo, 4 = indisponível
+#[derive(Debug, Deserialize)]
+pub struct AvailResponse {
+    pub status: i32,
+    pub fqdn: String,
+    #[serde(rename = "publication-status")]
+    pub publication_status: Option<String>,
+    #[serde(rename = "expires-at")]
+    pub expires_at: Option<String>,
+}
+
+/// Códigos de `status` da API de disponibilidade, com o status legível que
+/// [`AvailBackend`] monta para cada um e o que ele significa (para o `--explain`).
+///
+/// O endpoint não tem documentação pública, e esses são os códigos observados nas
+/// respostas. O 1 não aparece nelas; ele e qualquer outro código viram
+/// [`UNKNOWN_STATUS`], que nunca conta como disponível.
+pub const AVAIL_STATUS_LEGEND: &[(i32, &str, &str)] = &[
+    (0, "disponível", "livre para registro"),
+    (
+        2,
+        "registrado",
+        "já tem dono; a data de expiração aparece quando a API a informa",
+    ),
+    (
+        3,
+        "em processo",
+        "com um pedido de registro em andamento, ou em liberação quando o \
+         publication-status indica o processo de liberação",
+    ),
+    (
+        4,
+        "indisponível",
+        "não pode ser registrado (nome reservado ou bloqueado)",
+    ),
+];
+
+/// Início do status legível de códigos fora de [`AVAIL_STATUS_LEGEND`], seguido do
+/// código entre parênteses (`status desconhecido (1)`)
+pub const UNKNOWN_STATUS: &str = "status desconhecido";
+
+/// Valores de `publication-status` conhecidos e o que significam. O campo não é
+/// documentado pelo Registro.br, então a lista não é exaustiva e outros valores são
+/// mostrados como vieram.
+pub const PUBLICATION_STATUS_LEGEND: &[(&str, &str)] = &[
+    ("published", "publicado no DNS"),
+    ("waiting", "aguardando a conclusão do pedido de registro"),
+    (
+        "release-process",
+        "no processo de liberação: deixou de ser renovado e vai à disputa pública \
+         antes de ficar disponível",
+    ),
+];
+
+/// Acrescenta o `publication-status` ao status legível, quando a API o informa
+fn with_publication(status: &str, publication_status: &Option<String>) -> String {
+    match publication_status {
+        Some(publication) => format!("{} ({})", status, publication),
+        None => status.to_string(),
+    }
+}
+
+/// Trechos do `publication-status` que indicam o processo de liberação (a disputa
+/// pública por domínios que deixaram de ser renovados). O Registro.br não documenta
+/// os valores do campo, então qualquer um que contenha um destes trechos conta.
+const RELEASE_MARKERS: &[&str] = &[
+    "release",
+    "liberacao",
+    "liberação",
+    "competition",
+    "competicao",
+];
+
+/// Status legível de um domínio em processo de liberação
+pub const RELEASE_STATUS: &str = "em liberação";
+
+/// Indica se o `publication-status` corresponde ao processo de liberação
+pub fn is_release_phase(publication_status: &str) -> bool {
+    let publication_status = publication_status.to_lowercase();
+    RELEASE_MARKERS
+        .iter()
+        .any(|marker| publication_status.contains(marker))
+}
+
+/// Caracteres do corpo incluídos nas mensagens de erro de interpretação
+const BODY_SNIPPET_LEN: usize = 200;
+
+/// Início das mensagens de erro de respostas que não puderam ser interpretadas
+pub const PARSE_ERROR_PREFIX: &str = "parse error";
+
+/// Indica se `error` veio de uma resposta que não pôde ser interpretada (ver
+/// [`PARSE_ERROR_PREFIX`])
+pub fn is_parse_error(error: &str) -> bool {
+    error.starts_with(PARSE_ERROR_PREFIX)
+}
+
+/// Mensagem de erro de interpretação com o início do corpo recebido, para que se
+/// veja, por exemplo, que a API devolveu uma página HTML de bloqueio no lugar do JSON
+fn parse_error(error: serde_json::Error, body: &str) -> String {
+    let body = body.trim();
+    let mut snippet: String = body.chars().take(BODY_SNIPPET_LEN).collect();
+    if snippet.len() < body.len() {
+        snippet.push('…');
+    }
+    format!("{}: {} (corpo: {:?})", PARSE_ERROR_PREFIX, error, snippet)
+}
+
+/// Status legível de um domínio registrado, com a data de expiração quando conhecida
+fn registered_status(expires_at: Option<&str>) -> String {
+    match expires_at {
+        Some(expires) => format!(
+            "registrado (expira: {})",
+            expires.split('T').next().unwrap_or(expires)
+        ),
+        None => "registrado".to_string(),
+    }
+}
+
+/// Interpreta uma data de expiração em RFC 3339 (`2026-03-01T00:00:00-03:00`) ou só
+/// como data (`2026-03-01`, considerada meia-noite UTC)
+pub fn parse_expiry(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
+/// Dias inteiros entre `now` e a data de expiração, ou `None` se ela for inválida
+pub fn days_until_expiry(expires_at: &str, now: DateTime<Utc>) -> Option<i64> {
+    parse_expiry(expires_at).map(|expiry| (expiry - now).num_days())
+}
+
+/// Endpoint AJAX de disponibilidade usado pelo site do Registro.br (padrão)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AvailBackend;
+
+impl Backend for AvailBackend {
+    fn url(&self, domain: &str) -> String {
+        format!("{}{}", AVAIL_API_URL, domain)
+    }
+
+    fn interpret(&self, domain: String, status: StatusCode, body: &str) -> DomainResult {
+        if !status.is_success() {
+            return DomainResult::failed(domain, format!("HTTP {}", status));
+        }
+
+        let avail: AvailResponse = match serde_json::from_str(body) {
+            Ok(avail) => avail,
+            Err(e) => return DomainResult::failed(domain, parse_error(e, body)),
+        };
+
+        let status_str = match avail.status {
+            0 => "disponível".to_string(),
+            2 => registered_status(avail.expires_at.as_deref()),
+            3 if avail
+                .publication_status
+                .as_deref()
+                .is_some_and(is_release_phase) =>
+            {
+                with_publication(RELEASE_STATUS, &avail.publication_status)
+            }
+            3 => with_publication("em processo", &avail.publication_status),
+            4 => with_publication("indisponível", &avail.publication_status),
+            code => format!("{} ({})", UNKNOWN_STATUS, code),
+        };
+        DomainResult {
+            domain,
+            available: avail.status == 0,
+            status: Some(status_str),
+            publication_status: avail.publication_status,
+            status_code: Some(avail.status),
+            expires_in_days: avail
+                .expires_at
+                .as_deref()
+                .filter(|_| avail.status == 2)
+                .and_then(|expires| days_until_expiry(expires, Utc::now())),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RdapDomain {
+    #[serde(default)]
+    events: Vec<RdapEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RdapEvent {
+    #[serde(rename = "eventAction")]
+    action: String,
+    #[serde(rename = "eventDate")]
+    date: String,
+}
+
+/// Servidor RDAP oficial do Registro.br: 404 significa disponível e um objeto de
+/// domínio significa registrado
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RdapBackend;
+
+impl Backend for RdapBackend {
+    fn url(&self, domain: &str) -> String {
+        format!("{}{}", RDAP_API_URL, domain)
+    }
+
+    fn interpret(&self, domain: String, status: StatusCode, body: &str) -> DomainResult {
+        if status == StatusCode::NOT_FOUND {
+            return DomainResult {
+                domain,
+                available: true,
+                status: Some("disponível".to_string()),
+                ..Default::default()
+            };
+        }
+        if !status.is_success() {
+            return DomainResult::failed(domain, format!("HTTP {}", status));
+        }
+
+        match serde_json::from_str::<RdapDomain>(body) {
+            Ok(rdap) => {
+                let expires = rdap
+                    .events
+                    .iter()
+                    .find(|event| event.action == "expiration")
+                    .map(|event| event.date.as_str());
+                DomainResult {
+                    domain,
+                    status: Some(registered_status(expires)),
+                    expires_in_days: expires.and_then(|e| days_until_expiry(e, Utc::now())),
+                    ..Default::default()
+                }
+            }
+            Err(e) => DomainResult::failed(domain, parse_error(e, body)),
+        }
+    }
+}
+
+/// Valida o endereço base de uma API e garante a barra final, para que o FQDN possa
+/// ser acrescentado como último segmento do caminho
+pub fn parse_base_url(url: &str) -> Result<String> {
+    let parsed = Url::parse(url).with_context(|| format!("URL inválida: {}", url))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        bail!("A URL {} deve usar http:// ou https://", url);
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        bail!("A URL {} não pode ter query string nem fragmento", url);
+    }
+    let mut url = parsed.to_string();
+    if !url.ends_with('/') {
+        url.push('/');
+    }
+    Ok(url)
+}
+
+/// Consulta outro endereço (um espelho, um servidor de testes) interpretando as
+/// respostas como `inner`: a URL de cada domínio é `base_url` seguida do FQDN
+#[derive(Debug, Clone)]
+pub struct CustomUrl<B> {
+    base_url: String,
+    inner: B,
+}
+
+impl<B: Backend> CustomUrl<B> {
+    pub fn new(base_url: &str, inner: B) -> Result<Self> {
+        Ok(Self {
+            base_url: parse_base_url(base_url)?,
+            inner,
+        })
+    }
+}
+
+impl<B: Backend> Backend for CustomUrl<B> {
+    fn url(&self, domain: &str) -> String {
+        format!("{}{}", self.base_url, domain)
+    }
+
+    fn interpret(&self, domain: String, status: StatusCode, body: &str) -> DomainResult {
+        self.inner.interpret(domain, status, body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undocumented_status_is_unknown() {
+        for code in [1, 5, -1] {
+            let result = AvailBackend.interpret(
+                "loja.com.br".to_string(),
+                StatusCode::OK,
+                &format!(r#"{{"status": {}, "fqdn": "loja.com.br"}}"#, code),
+            );
+            assert!(!result.available);
+            assert_eq!(result.error, None);
+            assert_eq!(
+                result.status,
+                Some(format!("status desconhecido ({})", code))
+            );
+            assert_eq!(result.status_code, Some(code));
+            assert_eq!(result.kind(), crate::StatusKind::Unknown);
+        }
+    }
+
+    #[test]
+    fn test_avail_status_legend_matches_interpret() {
+        for &(code, label, _) in AVAIL_STATUS_LEGEND {
+            let result = AvailBackend.interpret(
+                "loja.com.br".to_string(),
+                StatusCode::OK,
+                &format!(r#"{{"status": {}, "fqdn": "loja.com.br"}}"#, code),
+            );
+            assert_eq!(result.status.as_deref(), Some(label));
+            assert_eq!(result.status_code, Some(code));
+        }
+        let (release, _) = PUBLICATION_STATUS_LEGEND
+            .iter()
+            .find(|(_, meaning)| meaning.contains("liberação"))
+            .unwrap();
+        assert!(is_release_phase(release));
+    }
+
+    #[test]
+    fn test_avail_backend_interpret() {
+        let result = AvailBackend.interpret(
+            "loja.com.br".to_string(),
+            StatusCode::OK,
+            r#"{"status": 2, "fqdn": "loja.com.br", "expires-at": "2026-03-01T00:00:00-03:00"}"#,
+        );
+        assert!(!result.available);
+        assert_eq!(result.expiry_date(), Some("2026-03-01"));
+        assert!(result.expires_in_days.is_some());
+
+        let result = AvailBackend.interpret(
+            "loja.com.br".to_string(),
+            StatusCode::OK,
+            r#"{"status": 2, "fqdn": "loja.com.br", "expires-at": "em breve"}"#,
+        );
+        assert_eq!(
+            result.status.as_deref(),
+            Some("registrado (expira: em breve)")
+        );
+        assert_eq!(result.expires_in_days, None);
+
+        let result = AvailBackend.interpret(
+            "loja.com.br".to_string(),
+            StatusCode::OK,
+            r#"{"status": 3, "fqdn": "loja.com.br", "publication-status": "waiting"}"#,
+        );
+        assert_eq!(result.status.as_deref(), Some("em processo (waiting)"));
+
+        let result = AvailBackend.interpret(
+            "loja.com.br".to_string(),
+            StatusCode::OK,
+            r#"{"status": 3, "fqdn": "loja.com.br", "publication-status": "release-process"}"#,
+        );
+        assert_eq!(
+            result.status.as_deref(),
+            Some("em liberação (release-process)")
+        );
+
+        let result = AvailBackend.interpret("loja.com.br".to_string(), StatusCode::OK, "<html>");
+        assert!(result.error.unwrap().starts_with("parse error"));
+    }
+
+    #[test]
+    fn test_parse_error_includes_body_snippet() {
+        let result = AvailBackend.interpret(
+            "loja.com.br".to_string(),
+            StatusCode::OK,
+            "  <html>Acesso bloqueado</html>\n",
+        );
+        let error = result.error.unwrap();
+        assert!(error.starts_with("parse error: expected value"));
+        assert!(error.ends_with(r#"(corpo: "<html>Acesso bloqueado</html>")"#));
+
+        // Corpos longos são truncados em 200 caracteres, sem cortar um caractere ao meio
+        let body = format!("<p>{}</p>", "ç".repeat(300));
+        let result = RdapBackend.interpret("loja.com.br".to_string(), StatusCode::OK, &body);
+        let error = result.error.unwrap();
+        let snippet = format!("<p>{}…", "ç".repeat(197));
+        assert!(error.ends_with(&format!("(corpo: {:?})", snippet)));
+    }
+
+    #[test]
+    fn test_custom_url() {
+        let backend = CustomUrl::new("http://127.0.0.1:8080/avail", AvailBackend).unwrap();
+        assert_eq!(
+            backend.url("loja.com.br"),
+            "http://127.0.0.1:8080/avail/loja.com.br"
+        );
+        let backend = CustomUrl::new("https://espelho.example/rdap/domain/", RdapBackend).unwrap();
+        assert_eq!(
+            backend.url("loja.com.br"),
+            "https://espelho.example/rdap/domain/loja.com.br"
+        );
+        assert!(
+            backend
+                .interpret("loja.com.br".to_string(), StatusCode::NOT_FOUND, "")
+                .available
+        );
+
+        assert!(parse_base_url("registro.br/v2").is_err());
+        assert!(parse_base_url("ftp://espelho.example/").is_err());
+        assert!(parse_base_url("https://espelho.example/?fqdn=").is_err());
+    }
+
+    #[test]
+    fn test_is_release_phase() {
+        assert!(is_release_phase("release-process"));
+        assert!(is_release_phase("Competition"));
+        assert!(is_release_phase("em-liberacao"));
+        assert!(!is_release_phase("waiting"));
+        assert!(!is_release_phase("published"));
+    }
+
+    #[test]
+    fn test_days_until_expiry() {
+        let now = parse_expiry("2026-01-01T12:00:00Z").unwrap();
+
+        assert_eq!(days_until_expiry("2026-01-31T12:00:00Z", now), Some(30));
+        assert_eq!(days_until_expiry("2026-01-11T00:00:00-03:00", now), Some(9));
+        assert_eq!(days_until_expiry("2026-01-02", now), Some(0));
+        assert_eq!(days_until_expiry("2025-12-01T12:00:00Z", now), Some(-31));
+        assert_eq!(days_until_expiry("01/02/2026", now), None);
+        assert_eq!(days_until_expiry("", now), None);
+    }
+
+    #[test]
+    fn test_rdap_backend_interpret() {
+        let result = RdapBackend.interpret("loja.com.br".to_string(), StatusCode::NOT_FOUND, "");
+        assert!(result.available);
+
+        let body = r#"{
+            "objectClassName": "domain",
+            "ldhName": "loja.com.br",
+            "status": ["active"],
+            "events": [
+                {"eventAction": "registration", "eventDate": "2010-01-01T12:00:00Z"},
+                {"eventAction": "expiration", "eventDate": "2026-01-01T12:00:00Z"}
+            ]
+        }"#;
+        let result = RdapBackend.interpret("loja.com.br".to_string(), StatusCode::OK, body);
+        assert!(!result.available);
+        assert_eq!(result.expiry_date(), Some("2026-01-01"));
+
+        let result = RdapBackend.interpret(
+            "loja.com.br".to_string(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "",
+        );
+        assert_eq!(
+            result.error.as_deref(),
+            Some("HTTP 500 Internal Server Error")
+        );
+    }
+}
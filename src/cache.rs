@@ -0,0 +1,140 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::DomainResult;
+
+/// Domínios registrados mudam pouco, então ficam em cache por mais tempo
+const UNAVAILABLE_TTL_FACTOR: u32 = 7;
+
+/// Cache compartilhado entre os workers
+pub type SharedResultCache = Arc<Mutex<ResultCache>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub available: bool,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub publication_status: Option<String>,
+    #[serde(default)]
+    pub status_code: Option<i32>,
+    /// Momento da verificação, em segundos desde a época Unix
+    pub checked_at: u64,
+}
+
+/// Cache de resultados persistido em JSON, indexado pelo FQDN
+#[derive(Debug)]
+pub struct ResultCache {
+    entries: HashMap<String, CacheEntry>,
+    ttl: Duration,
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+impl ResultCache {
+    /// Carrega o cache de `path`; um arquivo inexistente resulta em cache vazio
+    pub fn load(path: &str, ttl: Duration) -> Result<Self> {
+        let entries = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Falha ao interpretar cache {}", path))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e).with_context(|| format!("Falha ao ler cache {}", path)),
+        };
+        Ok(Self { entries, ttl })
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        let file = File::create(path).with_context(|| format!("Falha ao criar cache {}", path))?;
+        serde_json::to_writer(BufWriter::new(file), &self.entries)?;
+        Ok(())
+    }
+
+    pub fn shared(self) -> SharedResultCache {
+        Arc::new(Mutex::new(self))
+    }
+
+    /// Retorna a entrada de `domain` se ainda estiver dentro do TTL em `now`
+    pub fn get(&self, domain: &str, now: u64) -> Option<&CacheEntry> {
+        let entry = self.entries.get(domain)?;
+        let ttl = if entry.available {
+            self.ttl
+        } else {
+            self.ttl.saturating_mul(UNAVAILABLE_TTL_FACTOR)
+        };
+        (now.saturating_sub(entry.checked_at) < ttl.as_secs()).then_some(entry)
+    }
+
+    /// Registra um resultado; erros não são guardados para serem tentados de novo
+    pub fn insert(&mut self, result: &DomainResult, now: u64) {
+        if result.error.is_some() {
+            return;
+        }
+        self.entries.insert(
+            result.domain.clone(),
+            CacheEntry {
+                available: result.available,
+                status: result.status.clone(),
+                publication_status: result.publication_status.clone(),
+                status_code: result.status_code,
+                checked_at: now,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_cache(ttl_secs: u64) -> ResultCache {
+        ResultCache {
+            entries: HashMap::new(),
+            ttl: Duration::from_secs(ttl_secs),
+        }
+    }
+
+    fn result(domain: &str, available: bool, error: Option<&str>) -> DomainResult {
+        DomainResult {
+            domain: domain.to_string(),
+            available,
+            status: None,
+            error: error.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let mut cache = empty_cache(100);
+        cache.insert(&result("loja.com.br", true, None), 1_000);
+
+        assert!(cache.get("loja.com.br", 1_099).is_some());
+        assert!(cache.get("loja.com.br", 1_100).is_none());
+        assert!(cache.get("outra.com.br", 1_000).is_none());
+    }
+
+    #[test]
+    fn test_registered_entries_live_longer() {
+        let mut cache = empty_cache(100);
+        cache.insert(&result("loja.com.br", false, None), 1_000);
+
+        assert!(cache.get("loja.com.br", 1_500).is_some());
+        assert!(cache.get("loja.com.br", 1_700).is_none());
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let mut cache = empty_cache(100);
+        cache.insert(&result("loja.com.br", false, Some("HTTP 500")), 1_000);
+        assert!(cache.get("loja.com.br", 1_000).is_none());
+    }
+}
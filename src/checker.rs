@@ -0,0 +1,1364 @@
+use chrono::Utc;
+use futures::stream::{self, Stream, StreamExt};
+use rand::Rng;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, trace, warn};
+
+use crate::backend::{self, AvailBackend, Backend, RELEASE_STATUS};
+use crate::cache::{self, SharedResultCache};
+use crate::dns::DnsPrefilter;
+use crate::idn;
+use crate::limiter::{
+    RateLimiter, SharedCircuitBreaker, SharedConcurrency, SharedHedgeBudget, SharedRateLimiter,
+};
+use crate::metrics::SharedMetrics;
+use crate::middleware::Middleware;
+use crate::suffixes;
+
+/// Limite de novas tentativas após respostas 429, independente de `RetryPolicy::retries`
+const MAX_RATE_LIMIT_RETRIES: u32 = 10;
+
+/// Maior espera aceita de um Retry-After, para que um valor exagerado não pare os
+/// workers (e o Ctrl+C) por horas
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Domínio sabidamente registrado consultado por [`Checker::probe`]
+pub const PROBE_DOMAIN: &str = "registro.com.br";
+
+/// User-Agent enviado quando nenhum outro é configurado
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7)";
+
+/// User-Agents de navegadores comuns, alternados por
+/// [`Checker::with_rotating_user_agents`]
+pub const USER_AGENT_POOL: &[&str] = &[
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Safari/605.1.15",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:125.0) Gecko/20100101 Firefox/125.0",
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36 Edg/124.0.0.0",
+    "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:125.0) Gecko/20100101 Firefox/125.0",
+    "Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Mobile/15E148 Safari/604.1",
+];
+
+/// Política de novas tentativas com backoff exponencial e jitter
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Novas tentativas em falhas de rede transitórias
+    pub retries: u32,
+    /// Espera antes da primeira nova tentativa
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Espera antes da tentativa `attempt + 1`: base * 2^attempt, mais até 50% de jitter
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+        let jitter_ms = rand::rng().random_range(0..=backoff.as_millis() as u64 / 2);
+        backoff + Duration::from_millis(jitter_ms)
+    }
+}
+
+/// Interpreta o header Retry-After, em segundos ou como HTTP-date, limitado a
+/// [`MAX_RETRY_AFTER`]
+pub fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    let wait = match value.parse::<u64>() {
+        Ok(secs) => Duration::from_secs(secs),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            date.duration_since(now).unwrap_or(Duration::ZERO)
+        }
+    };
+    Some(wait.min(MAX_RETRY_AFTER))
+}
+
+/// Resultado da verificação de um domínio
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DomainResult {
+    /// FQDN verificado, com o sufixo
+    pub domain: String,
+    pub available: bool,
+    /// Status legível retornado pela API
+    pub status: Option<String>,
+    /// Erro que impediu a verificação, se houve
+    pub error: Option<String>,
+    /// Campo `publication-status` da API, quando presente
+    pub publication_status: Option<String>,
+    /// Forma Unicode do domínio, quando ele está em punycode (IDN)
+    pub unicode: Option<String>,
+    /// Dias até a expiração de um domínio registrado, contados a partir da consulta
+    /// (negativo quando já expirou); `None` se a API não informou uma data válida
+    pub expires_in_days: Option<i64>,
+    /// Restrição de registro do sufixo de um domínio disponível (`.adv.br` só para
+    /// advogados, por exemplo), segundo [`suffixes::restriction`]
+    pub restriction: Option<String>,
+    /// Código `status` bruto da API de disponibilidade (0, 2, 3, 4...), para filtrar
+    /// sem depender do texto de `status`; `None` com o backend RDAP, no pré-filtro de
+    /// DNS e quando a verificação falhou
+    pub status_code: Option<i32>,
+    /// Duração da consulta à API, incluindo novas tentativas (zero quando veio do
+    /// cache ou do pré-filtro de DNS). Não é gravada nos arquivos de saída.
+    #[serde(skip)]
+    pub latency: Duration,
+    /// Corpo da resposta da API, guardado só com [`Checker::with_raw_bodies`]. Não é
+    /// gravado nos arquivos de saída.
+    #[serde(skip)]
+    pub raw: Option<String>,
+}
+
+/// O que [`Checker::check_stream_cancellable`] faz com as consultas em andamento
+/// quando a varredura é cancelada
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnCancel {
+    /// Espera as consultas em andamento terminarem e produz os resultados delas
+    Finish,
+    /// Descarta as consultas em andamento; o stream termina na hora
+    Drop,
+}
+
+/// Categoria de um resultado, correspondente aos códigos de status da API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusKind {
+    /// status 0
+    Available,
+    /// status 2
+    Registered,
+    /// status 3
+    InProcess,
+    /// status 3 no processo de liberação, segundo o `publication-status`
+    Release,
+    /// status 4
+    Unavailable,
+    /// Qualquer outro código retornado pela API
+    Unknown,
+    /// A verificação falhou
+    Error,
+}
+
+impl DomainResult {
+    /// Resultado de uma verificação que falhou
+    pub fn failed(domain: String, error: impl Into<String>) -> Self {
+        Self {
+            domain,
+            error: Some(error.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Nome para exibição: o FQDN, seguido da forma Unicode quando for um IDN
+    pub fn display_name(&self) -> String {
+        match self.unicode {
+            Some(ref unicode) => format!("{} ({})", self.domain, unicode),
+            None => self.domain.clone(),
+        }
+    }
+
+    /// Como [`display_name`](Self::display_name), seguido da restrição de registro do
+    /// sufixo quando houver
+    pub fn display_name_with_restriction(&self) -> String {
+        match self.restriction {
+            Some(ref restriction) => {
+                format!(
+                    "{} [registro restrito: {}]",
+                    self.display_name(),
+                    restriction
+                )
+            }
+            None => self.display_name(),
+        }
+    }
+
+    /// Indica se o resultado veio do pré-filtro de DNS, sem consulta à API
+    pub fn is_dns_confirmed(&self) -> bool {
+        self.status.as_deref() == Some(DNS_REGISTERED_STATUS)
+    }
+
+    /// Categoria do resultado, pelo [`status_code`](Self::status_code) quando a API o
+    /// informou (veja [`backend::AVAIL_STATUS_LEGEND`]). Sem código (RDAP, pré-filtro de
+    /// DNS, cache antigo), pelo status montado em [`Checker::check`].
+    pub fn kind(&self) -> StatusKind {
+        if self.error.is_some() {
+            return StatusKind::Error;
+        }
+        if let Some(code) = self.status_code {
+            return match code {
+                0 => StatusKind::Available,
+                2 => StatusKind::Registered,
+                3 if self
+                    .publication_status
+                    .as_deref()
+                    .is_some_and(backend::is_release_phase) =>
+                {
+                    StatusKind::Release
+                }
+                3 => StatusKind::InProcess,
+                4 => StatusKind::Unavailable,
+                _ => StatusKind::Unknown,
+            };
+        }
+        if self.available {
+            return StatusKind::Available;
+        }
+        match self.status.as_deref() {
+            Some(status) if status.starts_with("registrado") => StatusKind::Registered,
+            Some(status) if status.starts_with("em processo") => StatusKind::InProcess,
+            Some(status) if status.starts_with(RELEASE_STATUS) => StatusKind::Release,
+            Some(status) if status.starts_with("indisponível") => StatusKind::Unavailable,
+            _ => StatusKind::Unknown,
+        }
+    }
+
+    /// Data de expiração (AAAA-MM-DD) de um domínio registrado, se informada
+    pub fn expiry_date(&self) -> Option<&str> {
+        self.status
+            .as_deref()?
+            .strip_prefix("registrado (expira: ")?
+            .strip_suffix(')')
+    }
+}
+
+/// Verificador de domínios: o cliente HTTP e a configuração compartilhada pelas
+/// consultas. Clonar é barato e os clones compartilham limitador e cache.
+#[derive(Debug, Clone)]
+pub struct Checker {
+    client: Client,
+    backend: Arc<dyn Backend>,
+    retry: RetryPolicy,
+    limiter: SharedRateLimiter,
+    cache: Option<SharedResultCache>,
+    dns: Option<DnsPrefilter>,
+    delay: Duration,
+    concurrency: Option<SharedConcurrency>,
+    breaker: Option<SharedCircuitBreaker>,
+    hedging: Option<SharedHedgeBudget>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    metrics: Option<SharedMetrics>,
+    /// Guarda o corpo de cada resposta em [`DomainResult::raw`]
+    keep_raw: bool,
+    /// Com [`with_parse_error_retries`](Self::with_parse_error_retries), respostas
+    /// ilegíveis também são tentadas de novo
+    retry_parse_errors: bool,
+    /// User-Agents usados em sequência, um por requisição
+    user_agents: Arc<[String]>,
+    /// Próxima posição em `user_agents`, compartilhada entre os clones
+    next_user_agent: Arc<AtomicUsize>,
+    /// Identificador da próxima verificação, para correlacionar os logs de uma mesma
+    /// consulta
+    next_request_id: Arc<AtomicUsize>,
+    /// Verificações em andamento agora, em todos os clones
+    in_flight: Arc<AtomicUsize>,
+}
+
+/// Status dos domínios confirmados como registrados pelo pré-filtro de DNS
+pub const DNS_REGISTERED_STATUS: &str = "registrado (DNS)";
+
+/// Erro dos domínios que continuaram recebendo 429 depois de todas as tentativas
+pub const RATE_LIMITED_ERROR: &str = "rate limited";
+
+impl Checker {
+    /// Cria um verificador com o backend padrão ([`AvailBackend`]), sem limite de taxa
+    /// e sem cache
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            backend: Arc::new(AvailBackend),
+            retry: RetryPolicy::default(),
+            limiter: RateLimiter::shared(None),
+            cache: None,
+            dns: None,
+            delay: Duration::ZERO,
+            concurrency: None,
+            breaker: None,
+            hedging: None,
+            metrics: None,
+            middleware: Vec::new(),
+            keep_raw: false,
+            retry_parse_errors: false,
+            user_agents: Arc::new([DEFAULT_USER_AGENT.to_string()]),
+            next_user_agent: Arc::new(AtomicUsize::new(0)),
+            next_request_id: Arc::new(AtomicUsize::new(1)),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn with_backend(mut self, backend: impl Backend + 'static) -> Self {
+        self.backend = Arc::new(backend);
+        self
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, limiter: SharedRateLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    pub fn with_cache(mut self, cache: SharedResultCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Consulta o DNS antes da API e marca como registrados os domínios que resolvem
+    pub fn with_dns_prefilter(mut self, dns: DnsPrefilter) -> Self {
+        self.dns = Some(dns);
+        self
+    }
+
+    /// Espera fixa antes de cada consulta à API. Como a espera ocupa a vaga do worker,
+    /// a taxa fica limitada a `concurrency / delay` requisições por segundo.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Ajusta o número de consultas simultâneas pelas respostas: em [`check_stream`],
+    /// `concurrency` passa a ser apenas o teto
+    ///
+    /// [`check_stream`]: Checker::check_stream
+    pub fn with_adaptive_concurrency(mut self, concurrency: SharedConcurrency) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Guarda o corpo de cada resposta da API no resultado, para inspecionar status
+    /// inesperados
+    pub fn with_raw_bodies(mut self, keep: bool) -> Self {
+        self.keep_raw = keep;
+        self
+    }
+
+    /// Trata respostas que não puderam ser interpretadas (uma página de erro HTML no
+    /// lugar do JSON, por exemplo) como falhas transitórias, tentadas de novo dentro de
+    /// [`RetryPolicy::retries`]. Só reconhece os erros com [`backend::PARSE_ERROR_PREFIX`].
+    pub fn with_parse_error_retries(mut self, retry: bool) -> Self {
+        self.retry_parse_errors = retry;
+        self
+    }
+
+    /// Registra o resultado de cada consulta à API no disjuntor; quem envia os
+    /// domínios decide parar quando ele abre
+    pub fn with_circuit_breaker(mut self, breaker: SharedCircuitBreaker) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// Envia uma requisição de reserva quando a resposta demora mais que
+    /// [`HedgeBudget::after`](crate::limiter::HedgeBudget::after), dentro do orçamento
+    pub fn with_hedging(mut self, hedging: SharedHedgeBudget) -> Self {
+        self.hedging = Some(hedging);
+        self
+    }
+
+    /// Acrescenta um [`Middleware`], chamado em cada requisição à API depois dos já
+    /// registrados
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Conta cada domínio verificado, as respostas 429 e a latência das consultas
+    pub fn with_metrics(mut self, metrics: SharedMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Envia sempre o mesmo User-Agent no lugar de [`DEFAULT_USER_AGENT`]
+    pub fn with_user_agent(self, user_agent: impl Into<String>) -> Self {
+        self.with_rotating_user_agents([user_agent])
+    }
+
+    /// Alterna entre os User-Agents de `pool` a cada requisição, em ordem (veja
+    /// [`USER_AGENT_POOL`]). Um `pool` vazio mantém o User-Agent atual.
+    pub fn with_rotating_user_agents<S: Into<String>>(
+        mut self,
+        pool: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let pool: Vec<String> = pool.into_iter().map(Into::into).collect();
+        if !pool.is_empty() {
+            self.user_agents = pool.into();
+            self.next_user_agent = Arc::new(AtomicUsize::new(0));
+        }
+        self
+    }
+
+    /// User-Agent da próxima requisição
+    fn next_user_agent(&self) -> &str {
+        let index = self.next_user_agent.fetch_add(1, Ordering::Relaxed);
+        &self.user_agents[index % self.user_agents.len()]
+    }
+
+    pub fn rate_limiter(&self) -> &SharedRateLimiter {
+        &self.limiter
+    }
+
+    /// Verifica `domain` + `suffix`, consultando o cache (se houver) antes da API.
+    ///
+    /// Cada chamada recebe um `request_id` crescente, presente nos logs da consulta. Os
+    /// eventos de início e fim (nível trace) trazem também quantas verificações estavam
+    /// em andamento, o que mostra quando as requisições se enfileiram.
+    pub async fn check(&self, domain: &str, suffix: &str) -> DomainResult {
+        let full_domain = format!("{}{}", domain, suffix);
+        let request_id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let in_flight = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        trace!(request_id, domain = %full_domain, in_flight, "início da verificação");
+        let started = Instant::now();
+
+        let mut result = self.lookup(request_id, full_domain).await;
+        if result.available {
+            result.restriction = suffixes::restriction(suffix).map(str::to_string);
+        }
+
+        let in_flight = self.in_flight.fetch_sub(1, Ordering::Relaxed) - 1;
+        trace!(
+            request_id,
+            domain = %result.domain,
+            in_flight,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "fim da verificação"
+        );
+        result
+    }
+
+    /// Consulta [`PROBE_DOMAIN`] direto na API, sem cache, pré-filtro de DNS nem
+    /// métricas, para confirmar antes de uma varredura que a conexão funciona e que a
+    /// resposta ainda tem o formato esperado
+    pub async fn probe(&self) -> DomainResult {
+        let request_id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        self.query(request_id, PROBE_DOMAIN.to_string()).await
+    }
+
+    /// Cache, pré-filtro de DNS e API, nessa ordem
+    async fn lookup(&self, request_id: usize, full_domain: String) -> DomainResult {
+        if let Some(ref cache) = self.cache {
+            if let Some(entry) = cache.lock().unwrap().get(&full_domain, cache::unix_now()) {
+                debug!(domain = %full_domain, "resultado obtido do cache");
+                let mut result = DomainResult {
+                    available: entry.available,
+                    status: entry.status.clone(),
+                    publication_status: entry.publication_status.clone(),
+                    status_code: entry.status_code,
+                    unicode: idn::to_unicode(&full_domain),
+                    domain: full_domain,
+                    ..Default::default()
+                };
+                // A data de expiração está no status guardado; os dias contam de agora
+                result.expires_in_days = result
+                    .expiry_date()
+                    .and_then(|date| backend::days_until_expiry(date, Utc::now()));
+                if let Some(ref metrics) = self.metrics {
+                    metrics.record(&result);
+                }
+                return result;
+            }
+        }
+
+        let mut result = match self.dns {
+            Some(ref dns) if dns.resolves(&full_domain).await => {
+                debug!(domain = %full_domain, "registrado segundo o DNS");
+                DomainResult {
+                    domain: full_domain,
+                    status: Some(DNS_REGISTERED_STATUS.to_string()),
+                    ..Default::default()
+                }
+            }
+            _ => {
+                if !self.delay.is_zero() {
+                    tokio::time::sleep(self.delay).await;
+                }
+                let started = Instant::now();
+                let mut result = self.query(request_id, full_domain).await;
+                result.latency = started.elapsed();
+                if let Some(ref breaker) = self.breaker {
+                    breaker.record(result.error.is_some());
+                }
+                result
+            }
+        };
+        result.unicode = idn::to_unicode(&result.domain);
+        if let Some(ref cache) = self.cache {
+            cache.lock().unwrap().insert(&result, cache::unix_now());
+        }
+        if let Some(ref metrics) = self.metrics {
+            metrics.record(&result);
+        }
+        result
+    }
+
+    /// Envia a requisição e, com [`with_hedging`](Self::with_hedging), uma reserva
+    /// idêntica se ela demorar; vale a primeira resposta e a outra é cancelada
+    async fn send(&self, url: &str) -> reqwest::Result<reqwest::Response> {
+        let request = || async {
+            let mut request = self
+                .client
+                .get(url)
+                .header(reqwest::header::USER_AGENT, self.next_user_agent());
+            for middleware in &self.middleware {
+                request = middleware.on_request(request);
+            }
+            let started = Instant::now();
+            let response = request.send().await;
+            if let Ok(ref response) = response {
+                for middleware in &self.middleware {
+                    middleware.on_response(response, started.elapsed());
+                }
+            }
+            response
+        };
+        let Some(ref hedging) = self.hedging else {
+            return request().await;
+        };
+
+        hedging.record_sent();
+        let primary = request();
+        tokio::pin!(primary);
+        tokio::select! {
+            response = &mut primary => return response,
+            _ = tokio::time::sleep(hedging.after()) => {}
+        }
+        if !hedging.try_hedge() {
+            return primary.await;
+        }
+        debug!(
+            url = %url,
+            after_ms = hedging.after().as_millis() as u64,
+            "sem resposta, enviando requisição de reserva"
+        );
+        tokio::select! {
+            response = primary => response,
+            response = request() => response,
+        }
+    }
+
+    /// Verifica cada par (nome, sufixo) de `targets`, produzindo os resultados à medida
+    /// que ficam prontos, fora da ordem de entrada.
+    ///
+    /// Até `concurrency` consultas ficam em andamento ao mesmo tempo. O stream é
+    /// preguiçoso: um novo alvo só é retirado de `targets` quando há vaga e o stream
+    /// está sendo consumido, então um consumidor lento segura as próximas requisições
+    /// em vez de acumular resultados em memória. Interromper o iterador de `targets`
+    /// (por exemplo com `take_while`) encerra o stream assim que as consultas em
+    /// andamento terminam.
+    ///
+    /// Com [`with_adaptive_concurrency`](Checker::with_adaptive_concurrency), cada
+    /// consulta aguarda também uma vaga no limite atual do controlador.
+    pub fn check_stream<I>(
+        &self,
+        targets: I,
+        concurrency: usize,
+    ) -> impl Stream<Item = DomainResult> + Send + 'static
+    where
+        I: IntoIterator<Item = (String, String)>,
+        I::IntoIter: Send + 'static,
+    {
+        let checker = self.clone();
+        stream::iter(targets)
+            .map(move |(domain, suffix)| {
+                let checker = checker.clone();
+                async move {
+                    let _permit = match checker.concurrency {
+                        Some(ref concurrency) => Some(concurrency.acquire().await),
+                        None => None,
+                    };
+                    checker.check(&domain, &suffix).await
+                }
+            })
+            .buffer_unordered(concurrency)
+    }
+
+    /// Como [`check_stream`](Self::check_stream), mas para de retirar alvos de
+    /// `targets` assim que `cancel` é cancelado. As consultas que já estavam em
+    /// andamento terminam normalmente ou são descartadas, conforme `on_cancel`.
+    pub fn check_stream_cancellable<I>(
+        &self,
+        targets: I,
+        concurrency: usize,
+        cancel: CancellationToken,
+        on_cancel: OnCancel,
+    ) -> impl Stream<Item = DomainResult> + Send + 'static
+    where
+        I: IntoIterator<Item = (String, String)>,
+        I::IntoIter: Send + 'static,
+    {
+        let feed = {
+            let cancel = cancel.clone();
+            targets
+                .into_iter()
+                .take_while(move |_| !cancel.is_cancelled())
+        };
+        let results = self.check_stream(feed, concurrency);
+        match on_cancel {
+            OnCancel::Finish => results.left_stream(),
+            OnCancel::Drop => results.take_until(cancel.cancelled_owned()).right_stream(),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn query(&self, request_id: usize, full_domain: String) -> DomainResult {
+        let url = self.backend.url(&full_domain);
+        let mut attempt = 0;
+        let mut rate_limited = 0;
+
+        loop {
+            let wait = self.limiter.lock().unwrap().reserve(Instant::now());
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+
+            trace!(url = %url, attempt, "enviando requisição");
+            let started = Instant::now();
+            match self.send(&url).await {
+                Ok(response) => {
+                    let status_code = response.status();
+                    debug!(
+                        url = %url,
+                        status = status_code.as_u16(),
+                        latency_ms = started.elapsed().as_millis() as u64,
+                        attempt,
+                        "resposta recebida"
+                    );
+
+                    if status_code == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                        self.limiter.lock().unwrap().on_rate_limited();
+                        if let Some(ref metrics) = self.metrics {
+                            metrics.record_rate_limited();
+                        }
+                        if let Some(ref concurrency) = self.concurrency {
+                            concurrency.on_congestion();
+                        }
+                        if rate_limited < MAX_RATE_LIMIT_RETRIES {
+                            // Respeita o Retry-After do servidor; sem ele, usa o backoff padrão
+                            let wait = response
+                                .headers()
+                                .get(reqwest::header::RETRY_AFTER)
+                                .and_then(|value| value.to_str().ok())
+                                .and_then(|value| parse_retry_after(value, SystemTime::now()))
+                                .unwrap_or_else(|| self.retry.delay(rate_limited));
+                            warn!(
+                                wait_ms = wait.as_millis() as u64,
+                                retry = rate_limited + 1,
+                                "rate limited, aguardando nova tentativa"
+                            );
+                            tokio::time::sleep(wait).await;
+                            rate_limited += 1;
+                            continue;
+                        }
+
+                        warn!("rate limited, tentativas esgotadas");
+                        return DomainResult::failed(full_domain, RATE_LIMITED_ERROR);
+                    }
+
+                    self.limiter.lock().unwrap().on_success();
+                    if let Some(ref concurrency) = self.concurrency {
+                        concurrency.on_success(started.elapsed());
+                    }
+                    // Só chega aqui sem --follow-redirects: um redirecionamento costuma
+                    // levar a uma página de login ou bloqueio, não a um resultado
+                    if status_code.is_redirection() {
+                        let location = response
+                            .headers()
+                            .get(reqwest::header::LOCATION)
+                            .and_then(|value| value.to_str().ok())
+                            .unwrap_or("-");
+                        warn!(
+                            status = status_code.as_u16(),
+                            location, "resposta redirecionada"
+                        );
+                        return DomainResult::failed(
+                            full_domain,
+                            format!("HTTP {} (redirecionado para {})", status_code, location),
+                        );
+                    }
+                    // O corpo é lido como texto antes de ser interpretado, para que
+                    // continue disponível mesmo quando não é o JSON esperado
+                    let result = match response.text().await {
+                        Ok(body) => {
+                            debug!(url = %url, body = %body, "corpo da resposta");
+                            let mut result =
+                                self.backend
+                                    .interpret(full_domain.clone(), status_code, &body);
+                            if self.keep_raw {
+                                result.raw = Some(body);
+                            }
+                            result
+                        }
+                        Err(e) => DomainResult::failed(full_domain.clone(), e.to_string()),
+                    };
+                    if let Some(ref error) = result.error {
+                        if self.retry_parse_errors
+                            && attempt < self.retry.retries
+                            && backend::is_parse_error(error)
+                        {
+                            let wait = self.retry.delay(attempt);
+                            warn!(
+                                error = %error,
+                                wait_ms = wait.as_millis() as u64,
+                                retry = attempt + 1,
+                                "resposta ilegível, aguardando nova tentativa"
+                            );
+                            tokio::time::sleep(wait).await;
+                            attempt += 1;
+                            continue;
+                        }
+                        warn!(status = status_code.as_u16(), error = %error, "resposta não aproveitada");
+                    }
+                    return result;
+                }
+                // Falhas de rede transitórias (timeout, conexão) são tentadas novamente
+                Err(e) if attempt < self.retry.retries && (e.is_timeout() || e.is_connect()) => {
+                    if let (true, Some(concurrency)) = (e.is_timeout(), &self.concurrency) {
+                        concurrency.on_congestion();
+                    }
+                    let wait = self.retry.delay(attempt);
+                    warn!(
+                        error = %e,
+                        latency_ms = started.elapsed().as_millis() as u64,
+                        wait_ms = wait.as_millis() as u64,
+                        retry = attempt + 1,
+                        "falha de rede, aguardando nova tentativa"
+                    );
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    if let (true, Some(concurrency)) = (e.is_timeout(), &self.concurrency) {
+                        concurrency.on_congestion();
+                    }
+                    warn!(error = %e, attempt, "falha na requisição");
+                    return DomainResult::failed(full_domain, e.to_string());
+                }
+            }
+        }
+    }
+}
+
+/// Verifica um único domínio com a configuração padrão de [`Checker`] (inclusive o
+/// [`DEFAULT_USER_AGENT`]). Para cabeçalhos próprios ou outro [`Middleware`], monte o
+/// `Checker` e use [`Checker::with_middleware`].
+pub async fn check_domain(client: &Client, domain: &str, suffix: &str) -> DomainResult {
+    Checker::new(client.clone()).check(domain, suffix).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_result_kind() {
+        let result = |available: bool, status: Option<&str>, error: Option<&str>| DomainResult {
+            domain: "loja.com.br".to_string(),
+            available,
+            status: status.map(str::to_string),
+            error: error.map(str::to_string),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            result(true, Some("disponível"), None).kind(),
+            StatusKind::Available
+        );
+        assert_eq!(
+            result(false, Some("registrado (expira: 2026-01-01)"), None).kind(),
+            StatusKind::Registered
+        );
+        assert_eq!(
+            result(false, Some(DNS_REGISTERED_STATUS), None).kind(),
+            StatusKind::Registered
+        );
+        assert!(result(false, Some(DNS_REGISTERED_STATUS), None).is_dns_confirmed());
+        assert_eq!(
+            result(false, Some("em processo"), None).kind(),
+            StatusKind::InProcess
+        );
+        assert_eq!(
+            result(false, Some("em liberação (release-process)"), None).kind(),
+            StatusKind::Release
+        );
+        assert_eq!(
+            result(false, Some("indisponível"), None).kind(),
+            StatusKind::Unavailable
+        );
+        assert_eq!(
+            result(false, Some("status desconhecido (7)"), None).kind(),
+            StatusKind::Unknown
+        );
+        assert_eq!(
+            result(false, None, Some("HTTP 500")).kind(),
+            StatusKind::Error
+        );
+    }
+
+    #[test]
+    fn test_domain_result_kind_prefers_status_code() {
+        let result = |code: i32, status: &str, publication_status: Option<&str>| DomainResult {
+            domain: "loja.com.br".to_string(),
+            available: code == 0,
+            status: Some(status.to_string()),
+            publication_status: publication_status.map(str::to_string),
+            status_code: Some(code),
+            ..Default::default()
+        };
+
+        // O texto não é consultado quando há código
+        assert_eq!(result(0, "?", None).kind(), StatusKind::Available);
+        assert_eq!(result(2, "?", None).kind(), StatusKind::Registered);
+        assert_eq!(
+            result(3, "?", Some("waiting")).kind(),
+            StatusKind::InProcess
+        );
+        assert_eq!(
+            result(3, "?", Some("release-process")).kind(),
+            StatusKind::Release
+        );
+        assert_eq!(result(4, "?", None).kind(), StatusKind::Unavailable);
+        assert_eq!(result(1, "registrado", None).kind(), StatusKind::Unknown);
+
+        let failed = DomainResult {
+            status_code: Some(2),
+            ..DomainResult::failed("loja.com.br".to_string(), "HTTP 500")
+        };
+        assert_eq!(failed.kind(), StatusKind::Error);
+    }
+
+    #[test]
+    fn test_user_agent_rotation_is_shared_between_clones() {
+        let checker = Checker::new(Client::new());
+        assert_eq!(checker.next_user_agent(), DEFAULT_USER_AGENT);
+
+        let checker = checker.with_rotating_user_agents(["a", "b", "c"]);
+        let clone = checker.clone();
+        assert_eq!(checker.next_user_agent(), "a");
+        assert_eq!(clone.next_user_agent(), "b");
+        assert_eq!(checker.next_user_agent(), "c");
+        assert_eq!(clone.next_user_agent(), "a");
+
+        let checker = checker
+            .with_user_agent("fixo")
+            .with_rotating_user_agents(Vec::<String>::new());
+        assert_eq!(checker.next_user_agent(), "fixo");
+        assert_eq!(checker.next_user_agent(), "fixo");
+    }
+
+    #[test]
+    fn test_retry_policy_delay_is_exponential_with_jitter() {
+        let retry = RetryPolicy {
+            retries: 3,
+            base_delay: Duration::from_millis(100),
+        };
+        for (attempt, base_ms) in [(0, 100), (1, 200), (2, 400)] {
+            let delay = retry.delay(attempt);
+            assert!(delay >= Duration::from_millis(base_ms));
+            assert!(delay <= Duration::from_millis(base_ms + base_ms / 2));
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = httpdate::parse_http_date("Tue, 21 Oct 2025 07:28:00 GMT").unwrap();
+        assert_eq!(parse_retry_after("45", now), Some(Duration::from_secs(45)));
+        assert_eq!(
+            parse_retry_after("Tue, 21 Oct 2025 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Tue, 21 Oct 2025 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("86400", now), Some(MAX_RETRY_AFTER));
+        assert_eq!(
+            parse_retry_after("Wed, 22 Oct 2025 07:28:00 GMT", now),
+            Some(MAX_RETRY_AFTER)
+        );
+        assert_eq!(parse_retry_after("amanhã", now), None);
+    }
+
+    /// Servidor local no formato do endpoint de disponibilidade
+    mod mock_api {
+        use super::*;
+        use crate::backend::CustomUrl;
+        use crate::cache::ResultCache;
+        use crate::limiter::HedgeBudget;
+        use crate::metrics::Metrics;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        fn checker(server: &MockServer, timeout: Duration) -> Checker {
+            let client = Client::builder().timeout(timeout).build().unwrap();
+            Checker::new(client)
+                .with_backend(CustomUrl::new(&server.uri(), AvailBackend).unwrap())
+                .with_retry(RetryPolicy {
+                    retries: 1,
+                    base_delay: Duration::from_millis(10),
+                })
+                // Taxa alta o bastante para que o AIMD não atrase os testes de 429
+                .with_rate_limiter(RateLimiter::shared(Some(100_000.0)))
+        }
+
+        async fn respond(server: &MockServer, domain: &str, response: ResponseTemplate) {
+            Mock::given(method("GET"))
+                .and(path(format!("/{}", domain)))
+                .respond_with(response)
+                .mount(server)
+                .await;
+        }
+
+        #[tokio::test]
+        async fn test_check_interprets_each_avail_status() {
+            let server = MockServer::start().await;
+            let json = |body: serde_json::Value| ResponseTemplate::new(200).set_body_json(body);
+            respond(
+                &server,
+                "livre.com.br",
+                json(serde_json::json!({"status": 0, "fqdn": "livre.com.br"})),
+            )
+            .await;
+            respond(
+                &server,
+                "loja.com.br",
+                json(serde_json::json!({
+                    "status": 2,
+                    "fqdn": "loja.com.br",
+                    "expires-at": "2030-03-01T00:00:00-03:00"
+                })),
+            )
+            .await;
+            respond(
+                &server,
+                "fila.com.br",
+                json(serde_json::json!({
+                    "status": 3,
+                    "fqdn": "fila.com.br",
+                    "publication-status": "waiting"
+                })),
+            )
+            .await;
+            respond(
+                &server,
+                "bloqueado.com.br",
+                json(serde_json::json!({"status": 4, "fqdn": "bloqueado.com.br"})),
+            )
+            .await;
+            let checker = checker(&server, Duration::from_secs(5));
+
+            let result = checker.check("livre", ".com.br").await;
+            assert_eq!(result.domain, "livre.com.br");
+            assert!(result.available);
+            assert_eq!(result.status.as_deref(), Some("disponível"));
+            assert_eq!(result.error, None);
+            assert!(!result.latency.is_zero());
+
+            let result = checker.check("loja", ".com.br").await;
+            assert!(!result.available);
+            assert_eq!(result.kind(), StatusKind::Registered);
+            assert_eq!(result.expiry_date(), Some("2030-03-01"));
+            assert!(result.expires_in_days.is_some_and(|days| days > 0));
+
+            let result = checker.check("fila", ".com.br").await;
+            assert_eq!(result.kind(), StatusKind::InProcess);
+            assert_eq!(result.status.as_deref(), Some("em processo (waiting)"));
+            assert_eq!(result.publication_status.as_deref(), Some("waiting"));
+
+            let result = checker.check("bloqueado", ".com.br").await;
+            assert_eq!(result.kind(), StatusKind::Unavailable);
+            assert!(!result.available);
+        }
+
+        #[tokio::test]
+        async fn test_check_notes_restricted_suffix_only_when_available() {
+            let server = MockServer::start().await;
+            let json = |body: serde_json::Value| ResponseTemplate::new(200).set_body_json(body);
+            respond(
+                &server,
+                "silva.adv.br",
+                json(serde_json::json!({"status": 0, "fqdn": "silva.adv.br"})),
+            )
+            .await;
+            respond(
+                &server,
+                "souza.adv.br",
+                json(serde_json::json!({"status": 2, "fqdn": "souza.adv.br"})),
+            )
+            .await;
+            let checker = checker(&server, Duration::from_secs(5));
+
+            let result = checker.check("silva", ".adv.br").await;
+            assert_eq!(
+                result.restriction.as_deref(),
+                Some("advogados com registro na OAB")
+            );
+            assert_eq!(
+                result.display_name_with_restriction(),
+                "silva.adv.br [registro restrito: advogados com registro na OAB]"
+            );
+            assert_eq!(checker.check("souza", ".adv.br").await.restriction, None);
+        }
+
+        #[tokio::test]
+        async fn test_check_hedges_slow_request() {
+            let server = MockServer::start().await;
+            let body = serde_json::json!({"status": 0, "fqdn": "livre.com.br"});
+            Mock::given(method("GET"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(&body)
+                        .set_delay(Duration::from_secs(2)),
+                )
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+                .mount(&server)
+                .await;
+            let hedging = HedgeBudget::shared(Duration::from_millis(50), 0.1, 1);
+            let checker = checker(&server, Duration::from_secs(5)).with_hedging(hedging.clone());
+
+            let result = checker.check("livre", ".com.br").await;
+            assert!(result.available);
+            assert!(
+                result.latency < Duration::from_secs(1),
+                "{:?}",
+                result.latency
+            );
+            assert_eq!(hedging.hedged(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_check_reports_redirect_as_error() {
+            let server = MockServer::start().await;
+            respond(
+                &server,
+                "loja.com.br",
+                ResponseTemplate::new(302).insert_header("location", "/login"),
+            )
+            .await;
+            let client = Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap();
+            let checker = Checker::new(client)
+                .with_backend(CustomUrl::new(&server.uri(), AvailBackend).unwrap());
+
+            let result = checker.check("loja", ".com.br").await;
+            assert_eq!(
+                result.error.as_deref(),
+                Some("HTTP 302 Found (redirecionado para /login)")
+            );
+        }
+
+        #[tokio::test]
+        async fn test_check_retries_after_rate_limit() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+                .up_to_n_times(2)
+                .mount(&server)
+                .await;
+            respond(
+                &server,
+                "loja.com.br",
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"status": 0, "fqdn": "loja.com.br"})),
+            )
+            .await;
+            let metrics = Metrics::shared();
+            let checker = checker(&server, Duration::from_secs(5)).with_metrics(metrics.clone());
+
+            let result = checker.check("loja", ".com.br").await;
+            assert!(result.available);
+            assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+            let text = metrics.render();
+            assert!(text.contains("registrobr_finder_checks_total 1\n"));
+            assert!(text.contains("registrobr_finder_rate_limited_total 2\n"));
+            assert!(text.contains("registrobr_finder_query_duration_seconds_count 1\n"));
+        }
+
+        #[tokio::test]
+        async fn test_check_gives_up_after_persistent_rate_limit() {
+            let server = MockServer::start().await;
+            respond(
+                &server,
+                "loja.com.br",
+                ResponseTemplate::new(429).insert_header("retry-after", "0"),
+            )
+            .await;
+            let checker = checker(&server, Duration::from_secs(5));
+
+            let result = checker.check("loja", ".com.br").await;
+            assert_eq!(result.kind(), StatusKind::Error);
+            assert_eq!(result.error.as_deref(), Some("rate limited"));
+            assert_eq!(
+                server.received_requests().await.unwrap().len(),
+                MAX_RATE_LIMIT_RETRIES as usize + 1
+            );
+        }
+
+        #[tokio::test]
+        async fn test_check_keeps_raw_body_when_asked() {
+            let server = MockServer::start().await;
+            let body = r#"{"status": 7, "fqdn": "loja.com.br"}"#;
+            respond(
+                &server,
+                "loja.com.br",
+                ResponseTemplate::new(200).set_body_string(body),
+            )
+            .await;
+
+            let result = checker(&server, Duration::from_secs(5))
+                .check("loja", ".com.br")
+                .await;
+            assert_eq!(result.kind(), StatusKind::Unknown);
+            assert_eq!(result.raw, None);
+
+            let result = checker(&server, Duration::from_secs(5))
+                .with_raw_bodies(true)
+                .check("loja", ".com.br")
+                .await;
+            assert_eq!(result.status.as_deref(), Some("status desconhecido (7)"));
+            assert_eq!(result.raw.as_deref(), Some(body));
+        }
+
+        #[tokio::test]
+        async fn test_check_reports_malformed_body() {
+            let server = MockServer::start().await;
+            respond(
+                &server,
+                "loja.com.br",
+                ResponseTemplate::new(200).set_body_string("<html>manutenção</html>"),
+            )
+            .await;
+            let checker = checker(&server, Duration::from_secs(5));
+
+            let result = checker.check("loja", ".com.br").await;
+            assert!(!result.available);
+            assert!(result.error.unwrap().starts_with("parse error"));
+        }
+
+        #[tokio::test]
+        async fn test_check_retries_parse_error_when_asked() {
+            let server = MockServer::start().await;
+            let html = || {
+                Mock::given(method("GET"))
+                    .respond_with(ResponseTemplate::new(200).set_body_string("<html>erro</html>"))
+                    .up_to_n_times(1)
+                    .with_priority(1)
+            };
+            html().mount(&server).await;
+            respond(
+                &server,
+                "loja.com.br",
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"status": 0, "fqdn": "loja.com.br"})),
+            )
+            .await;
+
+            // Sem a opção, a página HTML é o resultado final
+            let result = checker(&server, Duration::from_secs(5))
+                .check("loja", ".com.br")
+                .await;
+            assert!(result.error.unwrap().starts_with("parse error"));
+
+            html().mount(&server).await;
+            let checker = checker(&server, Duration::from_secs(5)).with_parse_error_retries(true);
+            let result = checker.check("loja", ".com.br").await;
+            assert_eq!(result.error, None);
+            assert!(result.available);
+            assert_eq!(server.received_requests().await.unwrap().len(), 3);
+        }
+
+        #[tokio::test]
+        async fn test_check_gives_up_on_persistent_parse_error() {
+            let server = MockServer::start().await;
+            respond(
+                &server,
+                "loja.com.br",
+                ResponseTemplate::new(200).set_body_string("<html>manutenção</html>"),
+            )
+            .await;
+            let checker = checker(&server, Duration::from_secs(5)).with_parse_error_retries(true);
+
+            let result = checker.check("loja", ".com.br").await;
+            assert!(result.error.unwrap().starts_with("parse error"));
+            // A tentativa original e a única nova tentativa do RetryPolicy do teste
+            assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        }
+
+        #[tokio::test]
+        async fn test_check_applies_middleware() {
+            #[derive(Debug)]
+            struct Auth {
+                statuses: Arc<std::sync::Mutex<Vec<u16>>>,
+            }
+
+            impl Middleware for Auth {
+                fn on_request(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+                    request.bearer_auth("segredo")
+                }
+
+                fn on_response(&self, response: &reqwest::Response, _: Duration) {
+                    self.statuses
+                        .lock()
+                        .unwrap()
+                        .push(response.status().as_u16());
+                }
+            }
+
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/loja.com.br"))
+                .and(header("authorization", "Bearer segredo"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(serde_json::json!({"status": 0, "fqdn": "loja.com.br"})),
+                )
+                .mount(&server)
+                .await;
+
+            let result = checker(&server, Duration::from_secs(5))
+                .check("loja", ".com.br")
+                .await;
+            assert_eq!(result.error.as_deref(), Some("HTTP 404 Not Found"));
+
+            let statuses = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let checker = checker(&server, Duration::from_secs(5)).with_middleware(Auth {
+                statuses: statuses.clone(),
+            });
+            let result = checker.check("loja", ".com.br").await;
+            assert!(result.available);
+            assert_eq!(*statuses.lock().unwrap(), [200]);
+        }
+
+        #[tokio::test]
+        async fn test_check_keeps_expiry_from_cache() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/loja.com.br"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "status": 2,
+                    "fqdn": "loja.com.br",
+                    "expires-at": "2030-03-01T00:00:00-03:00"
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+            let cache = ResultCache::load("/nonexistent/cache.json", Duration::from_secs(3600))
+                .unwrap()
+                .shared();
+            let checker = checker(&server, Duration::from_secs(5)).with_cache(cache);
+
+            let fresh = checker.check("loja", ".com.br").await;
+            let cached = checker.check("loja", ".com.br").await;
+            assert_eq!(cached.kind(), StatusKind::Registered);
+            assert!(cached.expires_in_days.is_some());
+            assert_eq!(cached.expires_in_days, fresh.expires_in_days);
+        }
+
+        #[tokio::test]
+        async fn test_probe_queries_known_domain() {
+            let server = MockServer::start().await;
+            let checker = checker(&server, Duration::from_secs(5));
+
+            // Sem resposta montada o wiremock devolve 404
+            let result = checker.probe().await;
+            assert_eq!(result.domain, PROBE_DOMAIN);
+            assert!(result.error.is_some());
+
+            respond(
+                &server,
+                PROBE_DOMAIN,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "status": 2,
+                    "fqdn": PROBE_DOMAIN,
+                })),
+            )
+            .await;
+            let result = checker.probe().await;
+            assert_eq!(result.error, None);
+            assert!(!result.available);
+        }
+
+        #[tokio::test]
+        async fn test_check_stream_cancellable() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(serde_json::json!({"status": 2, "fqdn": "loja.com.br"}))
+                        .set_delay(Duration::from_millis(300)),
+                )
+                .mount(&server)
+                .await;
+            let checker = checker(&server, Duration::from_secs(5));
+            let targets = || (0..10).map(|i| (format!("loja{}", i), ".com.br".to_string()));
+
+            for (on_cancel, expected) in [(OnCancel::Finish, 2), (OnCancel::Drop, 0)] {
+                let cancel = CancellationToken::new();
+                let stream =
+                    checker.check_stream_cancellable(targets(), 2, cancel.clone(), on_cancel);
+                let results = tokio::spawn(stream.collect::<Vec<_>>());
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                cancel.cancel();
+                let results = results.await.unwrap();
+                assert_eq!(results.len(), expected, "{:?}", on_cancel);
+                assert!(results.iter().all(|result| result.error.is_none()));
+            }
+        }
+
+        #[tokio::test]
+        async fn test_check_retries_then_reports_timeout() {
+            let server = MockServer::start().await;
+            respond(
+                &server,
+                "loja.com.br",
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"status": 0, "fqdn": "loja.com.br"}))
+                    .set_delay(Duration::from_secs(2)),
+            )
+            .await;
+            let checker = checker(&server, Duration::from_millis(100));
+
+            let result = checker.check("loja", ".com.br").await;
+            assert_eq!(result.kind(), StatusKind::Error);
+            assert!(!result.available);
+            // A tentativa original e a nova tentativa de RetryPolicy { retries: 1 }
+            assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        }
+
+        #[tokio::test]
+        async fn test_check_sends_default_user_agent() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(header("user-agent", DEFAULT_USER_AGENT))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(serde_json::json!({"status": 0, "fqdn": "loja.com.br"})),
+                )
+                .mount(&server)
+                .await;
+
+            let result = checker(&server, Duration::from_secs(5))
+                .check("loja", ".com.br")
+                .await;
+            assert!(result.available);
+        }
+    }
+}
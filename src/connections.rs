@@ -0,0 +1,100 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tower_layer::Layer;
+use tower_service::Service;
+use tracing::info;
+
+/// Conta as conexões abertas pelo cliente HTTP. O conector do reqwest só é chamado
+/// quando o pool não tem uma conexão ociosa para reaproveitar, então comparar o total
+/// com o número de requisições mostra se o keep-alive está funcionando.
+///
+/// Use com [`reqwest::ClientBuilder::connector_layer`].
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionCounter {
+    opened: Arc<AtomicUsize>,
+}
+
+impl ConnectionCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Conexões abertas até agora
+    pub fn opened(&self) -> usize {
+        self.opened.load(Ordering::Relaxed)
+    }
+}
+
+impl<S> Layer<S> for ConnectionCounter {
+    type Service = CountingConnector<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CountingConnector {
+            inner,
+            opened: self.opened.clone(),
+        }
+    }
+}
+
+/// Conector envolvido por [`ConnectionCounter`]
+#[derive(Debug, Clone)]
+pub struct CountingConnector<S> {
+    inner: S,
+    opened: Arc<AtomicUsize>,
+}
+
+impl<S, Request> Service<Request> for CountingConnector<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let opened = self.opened.fetch_add(1, Ordering::Relaxed) + 1;
+        info!(opened, "abrindo nova conexão (nenhuma ociosa no pool)");
+        self.inner.call(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::{ready, Ready};
+
+    #[derive(Clone)]
+    struct Echo;
+
+    impl Service<u32> for Echo {
+        type Response = u32;
+        type Error = ();
+        type Future = Ready<Result<u32, ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), ()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: u32) -> Self::Future {
+            ready(Ok(request))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_counter_counts_connector_calls() {
+        let counter = ConnectionCounter::new();
+        let mut first = counter.layer(Echo);
+        let mut second = counter.clone().layer(Echo);
+
+        assert_eq!(first.call(1).await, Ok(1));
+        assert_eq!(first.call(2).await, Ok(2));
+        assert_eq!(second.call(3).await, Ok(3));
+        assert_eq!(counter.opened(), 3);
+    }
+}
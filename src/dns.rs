@@ -0,0 +1,37 @@
+use anyhow::{Context, Result};
+use hickory_resolver::TokioAsyncResolver;
+
+/// Pré-filtro por DNS: um domínio com registros NS ou A certamente está registrado
+/// e dispensa a consulta à API. A ausência de registros não prova nada (domínios
+/// registrados podem estar sem delegação), então esses continuam indo para a API.
+#[derive(Clone)]
+pub struct DnsPrefilter {
+    resolver: TokioAsyncResolver,
+}
+
+impl std::fmt::Debug for DnsPrefilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DnsPrefilter").finish_non_exhaustive()
+    }
+}
+
+impl DnsPrefilter {
+    /// Usa os servidores DNS configurados no sistema
+    pub fn from_system_conf() -> Result<Self> {
+        let resolver = TokioAsyncResolver::tokio_from_system_conf()
+            .context("Falha ao ler a configuração de DNS do sistema")?;
+        Ok(Self { resolver })
+    }
+
+    /// Indica se `domain` tem registros NS ou A; erros de resolução contam como não
+    pub async fn resolves(&self, domain: &str) -> bool {
+        // O ponto final evita que os domínios de busca do sistema sejam anexados
+        let fqdn = format!("{}.", domain.trim_end_matches('.'));
+        let ns = self.resolver.ns_lookup(fqdn.as_str()).await;
+        if ns.is_ok_and(|ns| ns.iter().next().is_some()) {
+            return true;
+        }
+        let a = self.resolver.ipv4_lookup(fqdn.as_str()).await;
+        a.is_ok_and(|a| a.iter().next().is_some())
+    }
+}
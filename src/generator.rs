@@ -0,0 +1,763 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::ops::RangeInclusive;
+
+pub const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+pub const DIGITS: &str = "0123456789";
+
+/// Vogais e consoantes de [`generate_pronounceable`]. Ficam de fora as consoantes que
+/// quase nunca soam bem seguidas de uma vogal qualquer em português (`q`, `w`, `x`,
+/// `y`).
+pub const VOWELS: &str = "aeiou";
+pub const CONSONANTS: &str = "bcdfghjklmnprstvz";
+
+/// Letras da mais para a menos frequente em textos em português, para
+/// [`Combinations::with_char_order`]
+pub const PORTUGUESE_FREQUENCY: &str = "aeosrindmutclpvghqbfzjxkwy";
+
+/// Limites de tamanho de um nome sob .br, sem o sufixo
+pub const MIN_LABEL_LEN: usize = 2;
+pub const MAX_LABEL_LEN: usize = 26;
+
+/// Monta o conjunto de caracteres usado na geração das combinações.
+/// Um `charset` personalizado tem prioridade sobre `letters_only`/`numbers_only`,
+/// que não podem ser usados juntos.
+pub fn build_charset(
+    charset: Option<&str>,
+    letters_only: bool,
+    numbers_only: bool,
+) -> Result<Vec<char>> {
+    let Some(custom) = charset else {
+        if letters_only && numbers_only {
+            bail!("--letters e --numbers não podem ser usados juntos");
+        }
+        let chars = if numbers_only {
+            DIGITS.chars().collect()
+        } else if letters_only {
+            LETTERS.chars().collect()
+        } else {
+            LETTERS.chars().chain(DIGITS.chars()).collect()
+        };
+        return Ok(chars);
+    };
+
+    if custom.is_empty() {
+        bail!("O conjunto de caracteres (--charset) não pode ser vazio");
+    }
+
+    let mut chars = Vec::new();
+    for c in custom.chars() {
+        if !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+            bail!(
+                "Caractere inválido em --charset: {:?} (permitidos: a-z, 0-9 e -)",
+                c
+            );
+        }
+        if !chars.contains(&c) {
+            chars.push(c);
+        }
+    }
+
+    Ok(chars)
+}
+
+/// Cada palavra seguida dela mesma com cada dígito ao final: `loja`, `loja0`, ...,
+/// `loja9`
+pub fn mutate_with_digits<'a>(words: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    words
+        .into_iter()
+        .flat_map(|word| {
+            std::iter::once(word.to_string()).chain(
+                DIGITS
+                    .chars()
+                    .map(move |digit| format!("{}{}", word, digit)),
+            )
+        })
+        .collect()
+}
+
+/// Hífens são permitidos apenas no meio do rótulo e nunca em sequência
+pub fn has_valid_hyphens(label: &str) -> bool {
+    !label.starts_with('-') && !label.ends_with('-') && !label.contains("--")
+}
+
+/// Modelo em que `{}` é substituído por cada combinação gerada, como `loja{}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    before: String,
+    after: String,
+}
+
+impl Pattern {
+    /// Interpreta um modelo com exatamente um `{}`; o texto fixo aceita a-z, 0-9 e -
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let Some((before, after)) = pattern.split_once("{}") else {
+            bail!("O padrão {:?} precisa conter {{}}", pattern);
+        };
+        if after.contains("{}") {
+            bail!("O padrão {:?} deve conter apenas um {{}}", pattern);
+        }
+        if let Some(c) = before
+            .chars()
+            .chain(after.chars())
+            .find(|&c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'))
+        {
+            bail!(
+                "Caractere inválido no padrão {:?}: {:?} (permitidos: a-z, 0-9 e -)",
+                pattern,
+                c
+            );
+        }
+
+        // As combinações nunca começam nem terminam com hífen, então basta validar
+        // o texto fixo em volta de uma combinação qualquer
+        let parsed = Self {
+            before: before.to_string(),
+            after: after.to_string(),
+        };
+        if !has_valid_hyphens(&parsed.apply("a")) {
+            bail!(
+                "O padrão {:?} gera nomes com hífen no início, no fim ou duplicado",
+                pattern
+            );
+        }
+        Ok(parsed)
+    }
+
+    /// Garante que os nomes gerados com `lengths` respeitam os limites do .br
+    pub fn check_lengths(&self, lengths: &RangeInclusive<u8>) -> Result<()> {
+        let fixed = self.before.len() + self.after.len();
+        let (min, max) = (
+            fixed + *lengths.start() as usize,
+            fixed + *lengths.end() as usize,
+        );
+        if min < MIN_LABEL_LEN || max > MAX_LABEL_LEN {
+            bail!(
+                "O padrão gera nomes de {} a {} caracteres (permitido: {} a {})",
+                min,
+                max,
+                MIN_LABEL_LEN,
+                MAX_LABEL_LEN
+            );
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, combination: &str) -> String {
+        format!("{}{}{}", self.before, combination, self.after)
+    }
+}
+
+/// Filtro por expressões regulares sobre os nomes gerados (sem o sufixo): o nome
+/// precisa casar com `matches`, se houver, e não pode casar com `exclude`
+#[derive(Debug, Clone, Default)]
+pub struct LabelFilter {
+    matches: Option<Regex>,
+    exclude: Option<Regex>,
+}
+
+impl LabelFilter {
+    pub fn new(matches: Option<&str>, exclude: Option<&str>) -> Result<Self> {
+        let compile = |regex: Option<&str>, flag: &str| {
+            regex
+                .map(|regex| {
+                    Regex::new(regex)
+                        .with_context(|| format!("Expressão regular inválida em {}", flag))
+                })
+                .transpose()
+        };
+        Ok(Self {
+            matches: compile(matches, "--match")?,
+            exclude: compile(exclude, "--exclude")?,
+        })
+    }
+
+    /// Indica se algum filtro foi configurado
+    pub fn is_active(&self) -> bool {
+        self.matches.is_some() || self.exclude.is_some()
+    }
+
+    pub fn allows(&self, label: &str) -> bool {
+        self.matches.as_ref().is_none_or(|re| re.is_match(label))
+            && !self.exclude.as_ref().is_some_and(|re| re.is_match(label))
+    }
+}
+
+/// Iterador preguiçoso sobre as combinações, gerado por [`generate_combinations`]
+#[derive(Debug, Clone)]
+pub struct Combinations {
+    /// Caracteres possíveis em cada posição, alternados: a posição `i` usa
+    /// `alphabets[i % alphabets.len()]`
+    alphabets: Vec<Vec<char>>,
+    /// Restrições da primeira e da última posição (--first-char e --last-char)
+    first: Option<Vec<char>>,
+    last: Option<Vec<char>>,
+    lengths: RangeInclusive<u8>,
+    /// Caracteres de cada posição no tamanho atual, já com as restrições
+    slots: Vec<Vec<char>>,
+    /// Índice em `slots` de cada posição da combinação atual; vazio entre tamanhos
+    indices: Vec<usize>,
+    leading_zeros: bool,
+}
+
+impl Combinations {
+    /// Deixa de fora as combinações com mais de um caractere que começam com `0`
+    /// (`05`, `007`), como numa contagem sem zeros à esquerda
+    pub fn without_leading_zeros(mut self) -> Self {
+        self.leading_zeros = false;
+        self
+    }
+
+    /// Só gera combinações que começam com um dos caracteres de `chars`
+    pub fn with_first_chars(mut self, chars: &[char]) -> Self {
+        self.first = Some(chars.to_vec());
+        self
+    }
+
+    /// Só gera combinações que terminam com um dos caracteres de `chars`
+    pub fn with_last_chars(mut self, chars: &[char]) -> Self {
+        self.last = Some(chars.to_vec());
+        self
+    }
+
+    /// Percorre os caracteres de cada posição na ordem de `order` em vez da ordem do
+    /// conjunto: com [`PORTUGUESE_FREQUENCY`], `aa`, `ae`, `ao`... vêm antes de `ky`.
+    /// Caracteres que não estão em `order` vêm depois, na ordem original. O conjunto
+    /// gerado e o [`total`](Self::total) não mudam, só a sequência.
+    pub fn with_char_order(mut self, order: &[char]) -> Self {
+        for alphabet in &mut self.alphabets {
+            alphabet.sort_by_key(|c| order.iter().position(|o| o == c).unwrap_or(order.len()));
+        }
+        self
+    }
+
+    /// Quantidade de combinações que o iterador produz, calculada sem gerá-las. Vale
+    /// para um iterador que ainda não começou a ser consumido.
+    pub fn total(&self) -> u64 {
+        self.lengths
+            .clone()
+            .filter(|&length| length > 0)
+            .map(|length| {
+                // Prefixos válidos terminados em caractere comum e em hífen, como em
+                // has_valid_hyphens: o hífen nunca abre, fecha ou se repete
+                let slots = self.slots(length as usize);
+                let (mut plain, mut hyphen) = (0u64, 0u64);
+                for (position, slot) in slots.iter().enumerate() {
+                    let others = slot.iter().filter(|&&c| c != '-').count() as u64;
+                    if position == 0 {
+                        let zero =
+                            (!self.leading_zeros && length > 1 && slot.contains(&'0')) as u64;
+                        plain = others - zero;
+                        continue;
+                    }
+                    let next_hyphen = if slot.contains(&'-') { plain } else { 0 };
+                    plain = plain.saturating_add(hyphen).saturating_mul(others);
+                    hyphen = next_hyphen;
+                }
+                plain
+            })
+            .fold(0, u64::saturating_add)
+    }
+
+    /// Caracteres possíveis em cada posição de uma combinação de `length` caracteres
+    fn slots(&self, length: usize) -> Vec<Vec<char>> {
+        (0..length)
+            .map(|position| {
+                let restriction = [
+                    self.first.as_ref().filter(|_| position == 0),
+                    self.last.as_ref().filter(|_| position == length - 1),
+                ];
+                self.alphabets[position % self.alphabets.len()]
+                    .iter()
+                    .copied()
+                    .filter(|c| {
+                        restriction
+                            .iter()
+                            .flatten()
+                            .all(|allowed| allowed.contains(c))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Avança para a próxima combinação do tamanho atual, como um odômetro
+    fn advance(&mut self) -> bool {
+        for position in (0..self.indices.len()).rev() {
+            self.indices[position] += 1;
+            if self.indices[position] < self.slots[position].len() {
+                return true;
+            }
+            self.indices[position] = 0;
+        }
+        false
+    }
+}
+
+impl Iterator for Combinations {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            if self.indices.is_empty() {
+                let length = self.lengths.next()?;
+                self.slots = self.slots(length as usize);
+                if length == 0 || self.slots.iter().any(Vec::is_empty) {
+                    continue;
+                }
+                self.indices = vec![0; length as usize];
+            } else if !self.advance() {
+                self.indices.clear();
+                continue;
+            }
+
+            let combo: String = self
+                .indices
+                .iter()
+                .enumerate()
+                .map(|(position, &i)| self.slots[position][i])
+                .collect();
+            let padded = combo.len() > 1 && combo.starts_with('0');
+            if has_valid_hyphens(&combo) && (self.leading_zeros || !padded) {
+                return Some(combo);
+            }
+        }
+    }
+}
+
+/// Gera, sob demanda, todas as combinações de `chars` para cada tamanho em `lengths`,
+/// em ordem crescente de tamanho e, dentro de cada tamanho, na ordem de `chars`
+pub fn generate_combinations(lengths: RangeInclusive<u8>, chars: &[char]) -> Combinations {
+    Combinations {
+        alphabets: vec![chars.to_vec()],
+        first: None,
+        last: None,
+        lengths,
+        slots: Vec::new(),
+        indices: Vec::new(),
+        leading_zeros: true,
+    }
+}
+
+/// Alternativa a [`generate_combinations`] para nomes pronunciáveis: consoantes e
+/// vogais alternadas a partir de uma consoante (`ba`, `bel`, `tiko`), na mesma ordem
+/// de tamanho
+pub fn generate_pronounceable(lengths: RangeInclusive<u8>) -> Combinations {
+    Combinations {
+        alphabets: vec![CONSONANTS.chars().collect(), VOWELS.chars().collect()],
+        first: None,
+        last: None,
+        lengths,
+        slots: Vec::new(),
+        indices: Vec::new(),
+        leading_zeros: true,
+    }
+}
+
+/// Quantidade de nomes produzidos por [`generate_pronounceable`], sem gerá-los (ver
+/// [`Combinations::total`])
+pub fn count_pronounceable(lengths: RangeInclusive<u8>) -> u64 {
+    generate_pronounceable(lengths).total()
+}
+
+/// Quantidade de combinações produzidas por [`generate_combinations`], sem gerá-las
+/// (ver [`Combinations::total`])
+pub fn count_combinations(lengths: RangeInclusive<u8>, chars: &[char]) -> u64 {
+    generate_combinations(lengths, chars).total()
+}
+
+/// Como [`count_combinations`], para as combinações de
+/// [`Combinations::without_leading_zeros`]
+pub fn count_combinations_without_leading_zeros(
+    lengths: RangeInclusive<u8>,
+    chars: &[char],
+) -> u64 {
+    generate_combinations(lengths, chars)
+        .without_leading_zeros()
+        .total()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_combinations_2_digits_numbers_only() {
+        let combos: Vec<_> =
+            generate_combinations(2..=2, &build_charset(None, false, true).unwrap()).collect();
+        assert_eq!(combos.len(), 100); // 10^2
+        assert!(combos.contains(&"00".to_string()));
+        assert!(combos.contains(&"99".to_string()));
+        assert!(combos.contains(&"42".to_string()));
+    }
+
+    #[test]
+    fn test_generate_combinations_without_leading_zeros() {
+        let digits = build_charset(None, false, true).unwrap();
+
+        let combos: Vec<_> = generate_combinations(2..=2, &digits)
+            .without_leading_zeros()
+            .collect();
+        assert_eq!(combos.len(), 90); // 10 a 99
+        assert_eq!(combos.first(), Some(&"10".to_string()));
+        assert!(!combos.contains(&"00".to_string()));
+        assert!(!combos.contains(&"05".to_string()));
+
+        let combos: Vec<_> = generate_combinations(1..=3, &digits)
+            .without_leading_zeros()
+            .collect();
+        assert_eq!(combos.len(), 1000); // 0 a 999, sem repetições
+        assert_eq!(combos.first(), Some(&"0".to_string()));
+        assert_eq!(combos.last(), Some(&"999".to_string()));
+
+        // Zeros no meio e no fim continuam sendo gerados
+        let combos: Vec<_> = generate_combinations(3..=3, &['0', '1', 'a', '-'])
+            .without_leading_zeros()
+            .collect();
+        assert!(combos.contains(&"100".to_string()));
+        assert!(combos.contains(&"a-0".to_string()));
+        assert!(!combos.iter().any(|c| c.starts_with('0')));
+        assert_eq!(
+            count_combinations_without_leading_zeros(3..=3, &['0', '1', 'a', '-']),
+            combos.len() as u64
+        );
+    }
+
+    #[test]
+    fn test_count_combinations_without_leading_zeros() {
+        let digits = build_charset(None, false, true).unwrap();
+        assert_eq!(count_combinations_without_leading_zeros(2..=2, &digits), 90);
+        assert_eq!(
+            count_combinations_without_leading_zeros(1..=3, &digits),
+            1000
+        );
+        assert_eq!(
+            count_combinations_without_leading_zeros(2..=3, &digits),
+            990
+        );
+
+        // Sem o 0 no conjunto, nada muda
+        let letters = build_charset(None, true, false).unwrap();
+        assert_eq!(
+            count_combinations_without_leading_zeros(2..=3, &letters),
+            count_combinations(2..=3, &letters)
+        );
+    }
+
+    #[test]
+    fn test_generate_combinations_2_digits_letters_only() {
+        let combos: Vec<_> =
+            generate_combinations(2..=2, &build_charset(None, true, false).unwrap()).collect();
+        assert_eq!(combos.len(), 676); // 26^2
+        assert!(combos.contains(&"aa".to_string()));
+        assert!(combos.contains(&"zz".to_string()));
+        assert!(combos.contains(&"ab".to_string()));
+    }
+
+    #[test]
+    fn test_generate_combinations_2_digits_alphanumeric() {
+        let combos: Vec<_> =
+            generate_combinations(2..=2, &build_charset(None, false, false).unwrap()).collect();
+        assert_eq!(combos.len(), 1296); // 36^2
+        assert!(combos.contains(&"aa".to_string()));
+        assert!(combos.contains(&"00".to_string()));
+        assert!(combos.contains(&"a1".to_string()));
+        assert!(combos.contains(&"z9".to_string()));
+    }
+
+    #[test]
+    fn test_generate_combinations_3_digits_numbers_only() {
+        let combos: Vec<_> =
+            generate_combinations(3..=3, &build_charset(None, false, true).unwrap()).collect();
+        assert_eq!(combos.len(), 1000); // 10^3
+        assert!(combos.contains(&"000".to_string()));
+        assert!(combos.contains(&"999".to_string()));
+        assert!(combos.contains(&"123".to_string()));
+    }
+
+    #[test]
+    fn test_generate_combinations_3_digits_letters_only() {
+        let combos: Vec<_> =
+            generate_combinations(3..=3, &build_charset(None, true, false).unwrap()).collect();
+        assert_eq!(combos.len(), 17576); // 26^3
+        assert!(combos.contains(&"aaa".to_string()));
+        assert!(combos.contains(&"zzz".to_string()));
+        assert!(combos.contains(&"abc".to_string()));
+    }
+
+    #[test]
+    fn test_generate_combinations_3_digits_alphanumeric() {
+        let combos: Vec<_> =
+            generate_combinations(3..=3, &build_charset(None, false, false).unwrap()).collect();
+        assert_eq!(combos.len(), 46656); // 36^3
+    }
+
+    #[test]
+    fn test_generate_combinations_length_range_alphanumeric() {
+        let combos: Vec<_> =
+            generate_combinations(2..=3, &build_charset(None, false, false).unwrap()).collect();
+        assert_eq!(combos.len(), 1296 + 46656); // 36^2 + 36^3
+        assert_eq!(combos.first(), Some(&"aa".to_string()));
+        assert_eq!(combos.last(), Some(&"999".to_string()));
+    }
+
+    #[test]
+    fn test_generate_combinations_custom_charset() {
+        let chars = build_charset(Some("ab"), false, false).unwrap();
+        let combos: Vec<_> = generate_combinations(2..=2, &chars).collect();
+        assert_eq!(combos, vec!["aa", "ab", "ba", "bb"]);
+    }
+
+    #[test]
+    fn test_generate_pronounceable_alternates_consonants_and_vowels() {
+        let names: Vec<_> = generate_pronounceable(2..=4).collect();
+        assert_eq!(names.len() as u64, count_pronounceable(2..=4));
+        assert_eq!(names[0], "ba");
+        for name in ["bel", "tiko", "mano"] {
+            assert!(names.contains(&name.to_string()), "{}", name);
+        }
+        for name in &names {
+            assert!(name.chars().any(|c| VOWELS.contains(c)), "{}", name);
+            for (position, c) in name.chars().enumerate() {
+                let expected = if position % 2 == 0 {
+                    CONSONANTS
+                } else {
+                    VOWELS
+                };
+                assert!(expected.contains(c), "{}", name);
+            }
+        }
+        assert_eq!(count_pronounceable(3..=3), 17 * 5 * 17);
+    }
+
+    #[test]
+    fn test_build_charset_deduplicates_and_validates() {
+        assert_eq!(
+            build_charset(Some("abca1-"), true, true).unwrap(),
+            vec!['a', 'b', 'c', '1', '-']
+        );
+        assert!(build_charset(Some(""), false, false).is_err());
+        assert!(build_charset(Some("aB"), false, false).is_err());
+        assert!(build_charset(Some("a."), false, false).is_err());
+    }
+
+    #[test]
+    fn test_mutate_with_digits() {
+        let mutated = mutate_with_digits(["loja", "café"]);
+        assert_eq!(mutated.len(), 22);
+        assert_eq!(mutated[..3], ["loja", "loja0", "loja1"]);
+        assert_eq!(mutated[11..13], ["café", "café0"]);
+        assert_eq!(mutated[21], "café9");
+    }
+
+    #[test]
+    fn test_build_charset_rejects_letters_and_numbers_together() {
+        assert_eq!(build_charset(None, false, true).unwrap().len(), 10);
+        assert_eq!(build_charset(None, true, false).unwrap().len(), 26);
+        assert!(build_charset(None, true, true).is_err());
+    }
+
+    /// Implementação original, que materializava todas as combinações
+    fn reference_combinations(lengths: RangeInclusive<u8>, chars: &[char]) -> Vec<String> {
+        let mut combinations = Vec::new();
+        let base = chars.len();
+        for length in lengths {
+            for i in 0..base.pow(length as u32) {
+                let mut combo = String::with_capacity(length as usize);
+                let mut n = i;
+                for _ in 0..length {
+                    combo.push(chars[n % base]);
+                    n /= base;
+                }
+                let combo: String = combo.chars().rev().collect();
+                if has_valid_hyphens(&combo) {
+                    combinations.push(combo);
+                }
+            }
+        }
+        combinations
+    }
+
+    #[test]
+    fn test_generate_combinations_matches_reference_order() {
+        for (lengths, charset) in [
+            (1..=3, None),
+            (2..=2, Some("ab1")),
+            (1..=4, Some("ab-")),
+            (3..=3, Some("-x")),
+        ] {
+            let chars = build_charset(charset, false, false).unwrap();
+            let combos: Vec<_> = generate_combinations(lengths.clone(), &chars).collect();
+            assert_eq!(combos, reference_combinations(lengths, &chars));
+        }
+    }
+
+    #[test]
+    fn test_count_combinations() {
+        for (lengths, charset) in [
+            (2..=3, None),
+            (1..=5, Some("abc-")),
+            (4..=4, Some("-")),
+            (2..=2, Some("x")),
+        ] {
+            let chars = build_charset(charset, false, false).unwrap();
+            assert_eq!(
+                count_combinations(lengths.clone(), &chars),
+                generate_combinations(lengths, &chars).count() as u64
+            );
+        }
+        assert_eq!(
+            count_combinations(4..=4, &build_charset(None, false, false).unwrap()),
+            1_679_616
+        );
+    }
+
+    #[test]
+    fn test_pattern() {
+        let pattern = Pattern::parse("loja{}").unwrap();
+        assert_eq!(pattern.apply("ab"), "lojaab");
+        assert_eq!(Pattern::parse("app{}-br").unwrap().apply("01"), "app01-br");
+
+        assert!(Pattern::parse("loja").is_err());
+        assert!(Pattern::parse("{}loja{}").is_err());
+        assert!(Pattern::parse("Loja{}").is_err());
+        assert!(Pattern::parse("lo ja{}").is_err());
+        assert!(Pattern::parse("-loja{}").is_err());
+        assert!(Pattern::parse("loja--{}").is_err());
+    }
+
+    #[test]
+    fn test_label_filter() {
+        let chars: Vec<char> = LETTERS.chars().collect();
+        let filtered = |filter: &LabelFilter| {
+            generate_combinations(2..=2, &chars)
+                .filter(|label| filter.allows(label))
+                .collect::<Vec<_>>()
+        };
+
+        let vowels = LabelFilter::new(Some("^[aeiou]"), None).unwrap();
+        assert!(vowels.is_active());
+        let result = filtered(&vowels);
+        assert_eq!(result.len(), 5 * 26);
+        assert!(result.iter().all(|label| "aeiou".contains(&label[..1])));
+
+        let without_x = LabelFilter::new(None, Some("x")).unwrap();
+        assert_eq!(filtered(&without_x).len(), 25 * 25);
+
+        let both = LabelFilter::new(Some("^[aeiou]"), Some("a$")).unwrap();
+        let result = filtered(&both);
+        assert_eq!(result.len(), 5 * 25);
+        assert!(!result.contains(&"ea".to_string()));
+
+        let none = LabelFilter::default();
+        assert!(!none.is_active());
+        assert_eq!(filtered(&none).len(), 26 * 26);
+    }
+
+    #[test]
+    fn test_label_filter_rejects_invalid_regex() {
+        let err = LabelFilter::new(Some("[a-"), None).unwrap_err();
+        assert!(err.to_string().contains("--match"));
+        let err = LabelFilter::new(None, Some("(")).unwrap_err();
+        assert!(err.to_string().contains("--exclude"));
+    }
+
+    #[test]
+    fn test_pattern_check_lengths() {
+        let pattern = Pattern::parse("loja{}").unwrap();
+        assert!(pattern.check_lengths(&(1..=22)).is_ok());
+        assert!(pattern.check_lengths(&(1..=23)).is_err());
+        assert!(Pattern::parse("{}")
+            .unwrap()
+            .check_lengths(&(1..=2))
+            .is_err());
+    }
+
+    #[test]
+    fn test_generate_combinations_with_hyphens() {
+        let mut chars = build_charset(None, true, false).unwrap();
+        chars.push('-');
+        let combos: Vec<_> = generate_combinations(3..=3, &chars).collect();
+        assert!(combos.contains(&"a-b".to_string()));
+        assert!(!combos.contains(&"-ab".to_string()));
+        assert!(!combos.contains(&"ab-".to_string()));
+        assert!(!combos.contains(&"a--".to_string()));
+        assert!(combos.iter().all(|c| has_valid_hyphens(c)));
+        assert_eq!(combos.len(), 17576 + 26 * 26); // 26^3 + a-b
+    }
+
+    #[test]
+    fn test_char_order() {
+        assert_eq!(PORTUGUESE_FREQUENCY.len(), LETTERS.len());
+        let frequency: Vec<char> = PORTUGUESE_FREQUENCY.chars().collect();
+        let chars = build_charset(None, false, false).unwrap();
+
+        let ordered: Vec<_> = generate_combinations(2..=2, &chars)
+            .with_char_order(&frequency)
+            .collect();
+        assert_eq!(ordered[..3], ["aa", "ae", "ao"]);
+        // Os dígitos não estão na ordem e ficam no fim, na ordem original
+        assert_eq!(ordered[25..28], ["ay", "a0", "a1"]);
+        assert_eq!(ordered.last().unwrap(), "99");
+
+        let mut sorted = ordered.clone();
+        sorted.sort();
+        let mut plain: Vec<_> = generate_combinations(2..=2, &chars).collect();
+        plain.sort();
+        assert_eq!(sorted, plain);
+
+        // Ordem parcial: só `z` e `x` sobem para a frente
+        let partial = generate_pronounceable(2..=2).with_char_order(&['z', 'x', 'u']);
+        assert_eq!(partial.total(), 17 * 5);
+        let partial: Vec<_> = partial.collect();
+        assert_eq!(partial[..3], ["zu", "za", "ze"]);
+    }
+
+    #[test]
+    fn test_first_and_last_chars() {
+        let letters = build_charset(None, true, false).unwrap();
+        let vowels: Vec<char> = "aeiou".chars().collect();
+
+        let first = generate_combinations(2..=2, &letters).with_first_chars(&vowels);
+        assert_eq!(first.total(), 5 * 26);
+        let combos: Vec<_> = first.collect();
+        assert_eq!(combos.len(), 5 * 26);
+        assert_eq!(combos[0], "aa");
+        assert!(combos
+            .iter()
+            .all(|c| c.starts_with(|c| vowels.contains(&c))));
+
+        let both = generate_combinations(1..=3, &letters)
+            .with_first_chars(&vowels)
+            .with_last_chars(&['s']);
+        // Nenhuma com um caractere (precisaria estar nos dois conjuntos), "as".."us" com
+        // dois e vogal + letra + s com três
+        assert_eq!(both.total(), 5 + 5 * 26);
+        assert_eq!(both.clone().count() as u64, both.total());
+        assert_eq!(both.clone().next().as_deref(), Some("as"));
+
+        // Restrição fora do conjunto de caracteres não gera nada
+        let digits = build_charset(None, false, true).unwrap();
+        let none = generate_combinations(2..=2, &digits).with_first_chars(&vowels);
+        assert_eq!(none.total(), 0);
+        assert_eq!(none.count(), 0);
+    }
+
+    #[test]
+    fn test_total_matches_generated() {
+        let mut chars = build_charset(None, false, false).unwrap();
+        chars.push('-');
+        let cases = [
+            generate_combinations(1..=3, &chars),
+            generate_combinations(1..=3, &chars).without_leading_zeros(),
+            generate_combinations(1..=4, &chars)
+                .without_leading_zeros()
+                .with_last_chars(&['0', 'z']),
+            generate_pronounceable(1..=4),
+            generate_pronounceable(1..=4).with_first_chars(&['b', 'a']),
+        ];
+        for combinations in cases {
+            assert_eq!(combinations.total(), combinations.clone().count() as u64);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+
+use crate::DomainResult;
+
+/// Tabela e índice criados na primeira gravação; cada execução acrescenta uma linha
+/// por domínio verificado, identificada pelo horário de início da varredura
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS results (
+        id INTEGER PRIMARY KEY,
+        run_at TEXT NOT NULL,
+        domain TEXT NOT NULL,
+        available INTEGER NOT NULL,
+        status TEXT,
+        publication_status TEXT,
+        expires_at TEXT,
+        expires_in_days INTEGER,
+        error TEXT
+    );
+    CREATE INDEX IF NOT EXISTS results_domain ON results (domain);
+";
+
+/// Histórico de varreduras em SQLite, para acompanhar a mudança dos status entre
+/// execuções com SQL comum
+#[derive(Debug)]
+pub struct History {
+    conn: Connection,
+}
+
+impl History {
+    /// Abre (ou cria) o banco em `path`, criando o esquema se ainda não existir
+    pub fn open(path: &str) -> Result<Self> {
+        let conn =
+            Connection::open(path).with_context(|| format!("Falha ao abrir banco {}", path))?;
+        conn.execute_batch(SCHEMA)
+            .with_context(|| format!("Falha ao criar tabelas em {}", path))?;
+        Ok(Self { conn })
+    }
+
+    /// Grava os resultados de uma execução iniciada em `run_at` (RFC 3339) numa única
+    /// transação
+    pub fn record(&mut self, run_at: &str, results: &[DomainResult]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO results (run_at, domain, available, status, publication_status,
+                     expires_at, expires_in_days, error)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for result in results {
+                insert.execute(params![
+                    run_at,
+                    result.domain,
+                    result.available,
+                    result.status,
+                    result.publication_status,
+                    result.expiry_date(),
+                    result.expires_in_days,
+                    result.error,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_tracks_changes_between_runs() {
+        let mut history = History::open(":memory:").unwrap();
+        history
+            .record(
+                "2026-01-01T00:00:00Z",
+                &[DomainResult {
+                    domain: "loja.com.br".to_string(),
+                    status: Some("registrado (expira: 2026-01-10)".to_string()),
+                    expires_in_days: Some(9),
+                    ..Default::default()
+                }],
+            )
+            .unwrap();
+        history
+            .record(
+                "2026-02-01T00:00:00Z",
+                &[
+                    DomainResult {
+                        domain: "loja.com.br".to_string(),
+                        available: true,
+                        status: Some("disponível".to_string()),
+                        ..Default::default()
+                    },
+                    DomainResult::failed("teste.com.br".to_string(), "timeout"),
+                ],
+            )
+            .unwrap();
+
+        let expires_at: Option<String> = history
+            .conn
+            .query_row(
+                "SELECT expires_at FROM results WHERE run_at = '2026-01-01T00:00:00Z'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(expires_at.as_deref(), Some("2026-01-10"));
+
+        // Domínios que ficaram disponíveis depois de aparecerem como registrados
+        let freed: Vec<String> = history
+            .conn
+            .prepare(
+                "SELECT DISTINCT now.domain FROM results now
+                 JOIN results before ON before.domain = now.domain
+                     AND before.run_at < now.run_at
+                 WHERE now.available = 1 AND before.available = 0 AND before.error IS NULL",
+            )
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(freed, ["loja.com.br"]);
+    }
+}
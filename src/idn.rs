@@ -0,0 +1,36 @@
+use anyhow::{anyhow, Result};
+
+/// Converte um nome com acentos (IDN) para a forma ASCII/punycode aceita pela API.
+/// Nomes já em ASCII são apenas normalizados para minúsculas.
+pub fn to_ascii(domain: &str) -> Result<String> {
+    idna::domain_to_ascii(domain).map_err(|_| anyhow!("Nome de domínio inválido: {}", domain))
+}
+
+/// Forma Unicode de um nome em punycode, ou `None` se não houver rótulos `xn--`
+pub fn to_unicode(domain: &str) -> Option<String> {
+    if !domain.split('.').any(|label| label.starts_with("xn--")) {
+        return None;
+    }
+    match idna::domain_to_unicode(domain) {
+        (unicode, Ok(())) => Some(unicode),
+        (_, Err(_)) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_punycode_round_trip() {
+        let ascii = to_ascii("café.com.br").unwrap();
+        assert_eq!(ascii, "xn--caf-dma.com.br");
+        assert_eq!(to_unicode(&ascii).as_deref(), Some("café.com.br"));
+    }
+
+    #[test]
+    fn test_ascii_names_are_unchanged() {
+        assert_eq!(to_ascii("Loja").unwrap(), "loja");
+        assert_eq!(to_unicode("loja.com.br"), None);
+    }
+}
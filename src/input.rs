@@ -0,0 +1,257 @@
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+
+use crate::generator::{has_valid_hyphens, MAX_LABEL_LEN, MIN_LABEL_LEN};
+use crate::idn;
+use crate::suffixes::KNOWN_SUFFIXES;
+
+/// Remove de `name` o sufixo mais longo de `suffixes` que ele já contenha
+fn strip_known_suffix<'a>(name: &'a str, suffixes: &[String]) -> &'a str {
+    // O sufixo mais longo vence, para que ".com.br" não seja lido como ".br"
+    suffixes
+        .iter()
+        .filter_map(|suffix| name.strip_suffix(suffix.as_str()))
+        .min_by_key(|base| base.len())
+        .unwrap_or(name)
+}
+
+/// Normaliza um nome informado pelo usuário: minúsculas e punycode, sem o sufixo
+/// repetido, validado contra as regras de nomes do .br
+pub fn normalize_domain(entry: &str, suffixes: &[String]) -> Result<String> {
+    let entry = entry.trim();
+    let ascii = idn::to_ascii(entry)?;
+    let label = strip_known_suffix(&ascii, suffixes);
+
+    if label.contains('.') {
+        bail!(
+            "{:?} não é um nome simples (sufixos aceitos: {})",
+            entry,
+            suffixes.join(", ")
+        );
+    }
+    if let Some(c) = label
+        .chars()
+        .find(|&c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'))
+    {
+        bail!("{:?} contém caractere inválido: {:?}", entry, c);
+    }
+
+    // Nomes IDN são medidos e validados na forma Unicode
+    let (length, valid_hyphens) = match idn::to_unicode(label) {
+        Some(unicode) => (unicode.chars().count(), has_valid_hyphens(&unicode)),
+        None => (label.len(), has_valid_hyphens(label)),
+    };
+    if !(MIN_LABEL_LEN..=MAX_LABEL_LEN).contains(&length) {
+        bail!(
+            "{:?} tem {} caracteres (permitido: {} a {})",
+            entry,
+            length,
+            MIN_LABEL_LEN,
+            MAX_LABEL_LEN
+        );
+    }
+    if !valid_hyphens {
+        bail!("{:?} tem hífen no início, no fim ou duplicado", entry);
+    }
+
+    Ok(label.to_string())
+}
+
+/// Normaliza um nome que pode trazer o próprio sufixo. Um sufixo de `suffixes` é
+/// descartado, como em [`normalize_domain`], e o nome vale para todos eles (`None`).
+/// Qualquer outro sufixo conhecido do .br é mantido e fixa o nome nele: `loja.net.br`
+/// com `suffixes = [".com.br"]` é verificado só como `loja.net.br`.
+pub fn normalize_target(entry: &str, suffixes: &[String]) -> Result<(String, Option<String>)> {
+    let ascii = idn::to_ascii(entry.trim())?;
+    if strip_known_suffix(&ascii, suffixes).len() == ascii.len() {
+        // O sufixo conhecido mais longo vence, como em `strip_known_suffix`
+        let known = KNOWN_SUFFIXES
+            .iter()
+            .map(|&(suffix, _)| suffix)
+            .filter(|suffix| ascii.ends_with(suffix) && ascii.len() > suffix.len())
+            .max_by_key(|suffix| suffix.len());
+        if let Some(known) = known {
+            let label = normalize_domain(&ascii[..ascii.len() - known.len()], &[])?;
+            return Ok((label, Some(known.to_string())));
+        }
+    }
+    Ok((normalize_domain(entry, suffixes)?, None))
+}
+
+/// Monta os pares (nome, sufixo) a verificar a partir de `entries`: cada nome em
+/// todos os `suffixes`, ou só no sufixo que ele já trazia (veja [`normalize_target`]).
+/// Pares repetidos são descartados; os erros dos nomes inválidos são retornados à parte.
+pub fn normalize_targets<'a>(
+    entries: impl IntoIterator<Item = &'a str>,
+    suffixes: &[String],
+) -> (Vec<(String, String)>, Vec<anyhow::Error>) {
+    let mut targets: Vec<(String, String)> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut invalid = Vec::new();
+
+    for entry in entries {
+        if entry.trim().is_empty() {
+            continue;
+        }
+        let (label, pinned) = match normalize_target(entry, suffixes) {
+            Ok(target) => target,
+            Err(e) => {
+                invalid.push(e);
+                continue;
+            }
+        };
+        let entry_suffixes = match pinned {
+            Some(suffix) => vec![suffix],
+            None => suffixes.to_vec(),
+        };
+        for suffix in entry_suffixes {
+            let target = (label.clone(), suffix);
+            if seen.insert(target.clone()) {
+                targets.push(target);
+            }
+        }
+    }
+
+    (targets, invalid)
+}
+
+/// FQDNs a pular (--exclude-file), normalizados como em [`normalize_target`]: um nome
+/// simples vale em todos os `suffixes`, e um nome com sufixo exclui só aquele domínio
+/// (`loja.com.br` não tira `loja.net.br` da varredura). Os erros dos nomes inválidos
+/// são retornados à parte.
+pub fn normalize_exclusions<'a>(
+    entries: impl IntoIterator<Item = &'a str>,
+    suffixes: &[String],
+) -> (HashSet<String>, Vec<anyhow::Error>) {
+    let mut excluded = HashSet::new();
+    let mut invalid = Vec::new();
+
+    for entry in entries {
+        if entry.trim().is_empty() {
+            continue;
+        }
+        match normalize_target(entry, suffixes) {
+            Ok((label, Some(suffix))) => {
+                excluded.insert(format!("{}{}", label, suffix));
+            }
+            Ok((label, None)) => match idn::to_ascii(entry.trim()) {
+                // O sufixo configurado foi escrito: só esse domínio
+                Ok(ascii) if ascii != label => {
+                    excluded.insert(ascii);
+                }
+                _ => excluded.extend(suffixes.iter().map(|suffix| format!("{}{}", label, suffix))),
+            },
+            Err(e) => invalid.push(e),
+        }
+    }
+
+    (excluded, invalid)
+}
+
+/// Extrai os domínios de uma lista com um por linha, ignorando linhas vazias e
+/// comentários (#). Remove o sufixo quando já presente e descarta repetidos.
+pub fn parse_domain_list(content: &str, suffixes: &[String]) -> Vec<String> {
+    let mut domains: Vec<String> = Vec::new();
+    let mut seen = HashSet::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let domain = strip_known_suffix(line, suffixes);
+        if !domain.is_empty() && seen.insert(domain.to_string()) {
+            domains.push(domain.to_string());
+        }
+    }
+
+    domains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_domain_list() {
+        let content = "# meus domínios\nloja\n\n  loja.com.br  \nteste123\nloja\n";
+        assert_eq!(
+            parse_domain_list(content, &[".com.br".to_string()]),
+            vec!["loja", "teste123"]
+        );
+
+        let suffixes = [".br".to_string(), ".com.br".to_string()];
+        assert_eq!(parse_domain_list("loja.com.br\n", &suffixes), vec!["loja"]);
+    }
+
+    #[test]
+    fn test_normalize_domain() {
+        let suffixes = [".com.br".to_string()];
+        let (targets, invalid) = normalize_targets(
+            "loja, loja, LOJA,loja.com.br, ,café,a,lo_ja,-loja,loja.net".split(','),
+            &suffixes,
+        );
+
+        let labels: Vec<&str> = targets.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["loja", "xn--caf-dma"]);
+        assert_eq!(invalid.len(), 4);
+        assert!(normalize_domain(&"a".repeat(27), &suffixes).is_err());
+        assert!(normalize_domain(&"a".repeat(26), &suffixes).is_ok());
+    }
+
+    #[test]
+    fn test_normalize_target_keeps_other_known_suffixes() {
+        let suffixes = [".com.br".to_string()];
+        let target = |entry: &str| normalize_target(entry, &suffixes).unwrap();
+
+        assert_eq!(target("loja"), ("loja".to_string(), None));
+        assert_eq!(target("loja.com.br"), ("loja".to_string(), None));
+        assert_eq!(
+            target("loja.net.br"),
+            ("loja".to_string(), Some(".net.br".to_string()))
+        );
+        assert_eq!(
+            target("Café.blog.br"),
+            ("xn--caf-dma".to_string(), Some(".blog.br".to_string()))
+        );
+        assert!(normalize_target("loja.net", &suffixes).is_err());
+        assert!(normalize_target(".net.br", &suffixes).is_err());
+        assert!(normalize_target("a.net.br", &suffixes).is_err());
+    }
+
+    #[test]
+    fn test_normalize_targets() {
+        let suffixes = [".com.br".to_string(), ".org.br".to_string()];
+        let (targets, invalid) = normalize_targets(
+            ["loja", "loja.com.br", "loja.net.br", "loja.net.br", "x"],
+            &suffixes,
+        );
+
+        let pair = |label: &str, suffix: &str| (label.to_string(), suffix.to_string());
+        assert_eq!(
+            targets,
+            [
+                pair("loja", ".com.br"),
+                pair("loja", ".org.br"),
+                pair("loja", ".net.br")
+            ]
+        );
+        assert_eq!(invalid.len(), 1);
+    }
+
+    #[test]
+    fn test_normalize_exclusions() {
+        let suffixes = [".com.br".to_string(), ".net.br".to_string()];
+        let (excluded, invalid) =
+            normalize_exclusions(["Loja", "teste.com.br", "minha.rio.br", "a"], &suffixes);
+
+        let mut excluded: Vec<_> = excluded.into_iter().collect();
+        excluded.sort();
+        assert_eq!(
+            excluded,
+            ["loja.com.br", "loja.net.br", "minha.rio.br", "teste.com.br"]
+        );
+        assert_eq!(invalid.len(), 1);
+    }
+}
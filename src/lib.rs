@@ -0,0 +1,36 @@
+//! Verificação de disponibilidade de domínios `.br` usando a API do Registro.br.
+//!
+//! O binário `registrobr-finder` é uma CLI sobre esta biblioteca; o mesmo
+//! [`Checker`] pode ser usado diretamente por outros programas.
+
+pub mod backend;
+pub mod cache;
+pub mod checker;
+pub mod connections;
+pub mod dns;
+pub mod generator;
+pub mod history;
+pub mod idn;
+pub mod input;
+pub mod limiter;
+pub mod metrics;
+pub mod middleware;
+pub mod output;
+pub mod stats;
+pub mod suffixes;
+pub mod webhook;
+
+pub use backend::{AvailBackend, AvailResponse, Backend, RdapBackend};
+pub use checker::{
+    check_domain, Checker, DomainResult, OnCancel, RetryPolicy, StatusKind, DEFAULT_USER_AGENT,
+    DNS_REGISTERED_STATUS, PROBE_DOMAIN, USER_AGENT_POOL,
+};
+pub use generator::{
+    build_charset, count_combinations, count_combinations_without_leading_zeros,
+    count_pronounceable, generate_combinations, generate_pronounceable, mutate_with_digits,
+    LabelFilter, Pattern,
+};
+pub use middleware::Middleware;
+/// Aceito por [`Checker::check_stream_cancellable`]; reexportado para que quem usa a
+/// biblioteca não precise fixar a mesma versão do `tokio-util`
+pub use tokio_util::sync::CancellationToken;
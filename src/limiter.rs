@@ -0,0 +1,477 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{info, warn};
+
+/// Taxa (req/s) adotada no primeiro 429 quando não há limite definido
+const INITIAL_THROTTLED_RATE: f64 = 10.0;
+/// Taxa mínima, para que a varredura nunca pare por completo
+const MIN_RATE: f64 = 0.5;
+/// Aumento aditivo da taxa a cada resposta bem-sucedida
+const ADDITIVE_INCREASE: f64 = 0.1;
+/// Fator multiplicativo aplicado à taxa a cada 429
+const MULTIPLICATIVE_DECREASE: f64 = 0.5;
+
+/// Respostas mais lentas que isso não contam para aumentar a concorrência
+const FAST_RESPONSE: Duration = Duration::from_secs(2);
+
+/// Consultas consideradas pelo disjuntor: as mais recentes
+pub const BREAKER_WINDOW: usize = 100;
+/// Consultas mínimas na janela antes de o disjuntor poder abrir
+pub const BREAKER_MIN_SAMPLES: usize = 20;
+
+/// Fração das requisições que pode ganhar uma reserva (hedging)
+pub const HEDGE_RATIO: f64 = 0.1;
+/// Reservas permitidas antes de haver requisições suficientes para a fração valer
+pub const HEDGE_BURST: usize = 5;
+
+/// Limitador compartilhado entre os workers
+pub type SharedRateLimiter = Arc<Mutex<RateLimiter>>;
+
+/// Controlador AIMD da taxa de requisições: reduz a taxa pela metade a cada
+/// resposta 429 e volta a aumentá-la aos poucos enquanto as respostas são aceitas.
+#[derive(Debug)]
+pub struct RateLimiter {
+    rate: f64,
+    max_rate: f64,
+    next_slot: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Cria um limitador com teto de `max_rps` requisições por segundo.
+    /// Sem teto, as requisições são liberadas até o primeiro 429.
+    pub fn new(max_rps: Option<f64>) -> Self {
+        let max_rate = max_rps.unwrap_or(f64::INFINITY);
+        Self {
+            rate: max_rate,
+            max_rate,
+            next_slot: None,
+        }
+    }
+
+    pub fn shared(max_rps: Option<f64>) -> SharedRateLimiter {
+        Arc::new(Mutex::new(Self::new(max_rps)))
+    }
+
+    /// Taxa atual em requisições por segundo
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn on_success(&mut self) {
+        self.rate = (self.rate + ADDITIVE_INCREASE).min(self.max_rate);
+    }
+
+    pub fn on_rate_limited(&mut self) {
+        self.rate = if self.rate.is_finite() {
+            (self.rate * MULTIPLICATIVE_DECREASE).max(MIN_RATE)
+        } else {
+            INITIAL_THROTTLED_RATE
+        };
+    }
+
+    /// Reserva o próximo horário de envio e retorna quanto o chamador deve esperar
+    pub fn reserve(&mut self, now: Instant) -> Duration {
+        if !self.rate.is_finite() {
+            return Duration::ZERO;
+        }
+
+        let slot = self.next_slot.map_or(now, |next| next.max(now));
+        self.next_slot = Some(slot + Duration::from_secs_f64(1.0 / self.rate));
+        slot - now
+    }
+}
+
+/// Concorrência adaptativa compartilhada entre os workers
+pub type SharedConcurrency = Arc<AdaptiveConcurrency>;
+
+/// Controlador AIMD do número de consultas simultâneas (`--workers auto`): a cada
+/// rodada de respostas rápidas aceita mais uma consulta, e reduz o limite pela metade
+/// a cada 429 ou timeout, sempre entre 1 e `max`.
+///
+/// O limite é aplicado por um semáforo. Aumentar libera uma permissão nova; reduzir
+/// descarta permissões livres e, se todas estão em uso, as que forem devolvidas.
+#[derive(Debug)]
+pub struct AdaptiveConcurrency {
+    semaphore: Arc<Semaphore>,
+    state: Mutex<ConcurrencyState>,
+    max: usize,
+}
+
+#[derive(Debug)]
+struct ConcurrencyState {
+    limit: usize,
+    /// Respostas rápidas desde o último ajuste
+    successes: usize,
+    /// Permissões em uso que devem ser descartadas ao serem devolvidas
+    debt: usize,
+}
+
+/// Permissão para uma consulta; devolvida (ou descartada) ao sair de escopo
+#[derive(Debug)]
+pub struct ConcurrencyPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    concurrency: SharedConcurrency,
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        let mut state = self.concurrency.state.lock().unwrap();
+        if let Some(permit) = self.permit.take() {
+            if state.debt > 0 {
+                state.debt -= 1;
+                permit.forget();
+            }
+        }
+    }
+}
+
+impl AdaptiveConcurrency {
+    /// Começa com `initial` consultas simultâneas, podendo chegar a `max`
+    pub fn shared(initial: usize, max: usize) -> SharedConcurrency {
+        let max = max.max(1);
+        let initial = initial.clamp(1, max);
+        Arc::new(Self {
+            semaphore: Arc::new(Semaphore::new(initial)),
+            state: Mutex::new(ConcurrencyState {
+                limit: initial,
+                successes: 0,
+                debt: 0,
+            }),
+            max,
+        })
+    }
+
+    /// Limite atual de consultas simultâneas
+    pub fn limit(&self) -> usize {
+        self.state.lock().unwrap().limit
+    }
+
+    /// Limite máximo, usado como tamanho do buffer de consultas
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Aguarda uma vaga dentro do limite atual
+    pub async fn acquire(self: &Arc<Self>) -> ConcurrencyPermit {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("o semáforo nunca é fechado");
+        ConcurrencyPermit {
+            permit: Some(permit),
+            concurrency: self.clone(),
+        }
+    }
+
+    pub fn on_success(&self, latency: Duration) {
+        if latency > FAST_RESPONSE {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.successes += 1;
+        if state.successes < state.limit || state.limit >= self.max {
+            return;
+        }
+        state.successes = 0;
+        state.limit += 1;
+        // Uma permissão devolvida a menos equivale a uma permissão nova
+        if state.debt > 0 {
+            state.debt -= 1;
+        } else {
+            self.semaphore.add_permits(1);
+        }
+        info!(workers = state.limit, "concorrência aumentada");
+    }
+
+    /// Chamado a cada 429 ou timeout
+    pub fn on_congestion(&self) {
+        let mut state = self.state.lock().unwrap();
+        let limit = (state.limit / 2).max(1);
+        let excess = state.limit - limit;
+        if excess == 0 {
+            return;
+        }
+        state.limit = limit;
+        state.successes = 0;
+        let forgotten = self.semaphore.forget_permits(excess);
+        state.debt += excess - forgotten;
+        info!(workers = limit, "concorrência reduzida");
+    }
+}
+
+/// Disjuntor compartilhado entre os workers
+pub type SharedCircuitBreaker = Arc<CircuitBreaker>;
+
+/// Disjuntor da varredura: acompanha a taxa de erros nas últimas `window` consultas
+/// e abre quando ela passa de `max_error_rate`, depois de pelo menos `min_samples`
+/// consultas. Aberto, continua aberto: a varredura deve parar de enviar domínios,
+/// porque um bloqueio do IP ou uma indisponibilidade da API faria todo o resto falhar.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    max_error_rate: f64,
+    window: usize,
+    min_samples: usize,
+    /// Resultado das consultas mais recentes (`true` = erro)
+    outcomes: Mutex<VecDeque<bool>>,
+    open: AtomicBool,
+}
+
+impl CircuitBreaker {
+    pub fn shared(max_error_rate: f64, window: usize, min_samples: usize) -> SharedCircuitBreaker {
+        let window = window.max(1);
+        Arc::new(Self {
+            max_error_rate,
+            window,
+            min_samples: min_samples.clamp(1, window),
+            outcomes: Mutex::new(VecDeque::with_capacity(window)),
+            open: AtomicBool::new(false),
+        })
+    }
+
+    /// Registra o resultado de uma consulta à API
+    pub fn record(&self, failed: bool) {
+        let mut outcomes = self.outcomes.lock().unwrap();
+        if outcomes.len() == self.window {
+            outcomes.pop_front();
+        }
+        outcomes.push_back(failed);
+
+        if outcomes.len() < self.min_samples || self.open.load(Ordering::Relaxed) {
+            return;
+        }
+        let rate = error_rate(&outcomes);
+        if rate > self.max_error_rate {
+            self.open.store(true, Ordering::Relaxed);
+            warn!(
+                error_rate = rate,
+                samples = outcomes.len(),
+                "disjuntor aberto: taxa de erros acima do limite"
+            );
+        }
+    }
+
+    /// Indica se a taxa de erros já passou do limite
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::Relaxed)
+    }
+
+    /// Taxa de erros atual e o número de consultas em que foi medida
+    pub fn error_rate(&self) -> (f64, usize) {
+        let outcomes = self.outcomes.lock().unwrap();
+        (error_rate(&outcomes), outcomes.len())
+    }
+}
+
+/// Orçamento de requisições de reserva compartilhado entre os workers
+pub type SharedHedgeBudget = Arc<HedgeBudget>;
+
+/// Requisições de reserva (hedging): quando uma consulta passa de `after` sem resposta,
+/// uma segunda idêntica é enviada e vale a que responder primeiro. Para que a carga
+/// extra não se multiplique quando a API inteira fica lenta, as reservas ficam
+/// limitadas a `ratio` das requisições enviadas, com uma folga inicial de `burst`.
+#[derive(Debug)]
+pub struct HedgeBudget {
+    after: Duration,
+    ratio: f64,
+    burst: usize,
+    sent: AtomicUsize,
+    hedged: AtomicUsize,
+}
+
+impl HedgeBudget {
+    pub fn shared(after: Duration, ratio: f64, burst: usize) -> SharedHedgeBudget {
+        Arc::new(Self {
+            after,
+            ratio,
+            burst,
+            sent: AtomicUsize::new(0),
+            hedged: AtomicUsize::new(0),
+        })
+    }
+
+    /// Espera sem resposta a partir da qual vale enviar uma reserva
+    pub fn after(&self) -> Duration {
+        self.after
+    }
+
+    /// Conta uma requisição enviada normalmente
+    pub fn record_sent(&self) {
+        self.sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Reserva espaço no orçamento para uma requisição de reserva, se houver
+    pub fn try_hedge(&self) -> bool {
+        let allowed = self.burst + (self.sent.load(Ordering::Relaxed) as f64 * self.ratio) as usize;
+        self.hedged
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |hedged| {
+                (hedged < allowed).then_some(hedged + 1)
+            })
+            .is_ok()
+    }
+
+    /// Requisições de reserva enviadas até agora
+    pub fn hedged(&self) -> usize {
+        self.hedged.load(Ordering::Relaxed)
+    }
+}
+
+fn error_rate(outcomes: &VecDeque<bool>) -> f64 {
+    if outcomes.is_empty() {
+        return 0.0;
+    }
+    outcomes.iter().filter(|&&failed| failed).count() as f64 / outcomes.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_adaptive_concurrency_increases_after_a_round_of_fast_responses() {
+        let concurrency = AdaptiveConcurrency::shared(2, 3);
+        assert_eq!(concurrency.semaphore.available_permits(), 2);
+
+        concurrency.on_success(Duration::from_secs(5));
+        concurrency.on_success(Duration::from_millis(100));
+        assert_eq!(concurrency.limit(), 2);
+        concurrency.on_success(Duration::from_millis(100));
+        assert_eq!(concurrency.limit(), 3);
+        assert_eq!(concurrency.semaphore.available_permits(), 3);
+
+        for _ in 0..10 {
+            concurrency.on_success(Duration::from_millis(100));
+        }
+        assert_eq!(concurrency.limit(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_concurrency_decrease_takes_effect_as_permits_return() {
+        let concurrency = AdaptiveConcurrency::shared(4, 8);
+        let permits = vec![
+            concurrency.acquire().await,
+            concurrency.acquire().await,
+            concurrency.acquire().await,
+        ];
+
+        // Uma permissão livre é descartada na hora; a outra, quando for devolvida
+        concurrency.on_congestion();
+        assert_eq!(concurrency.limit(), 2);
+        assert_eq!(concurrency.semaphore.available_permits(), 0);
+
+        drop(permits);
+        assert_eq!(concurrency.semaphore.available_permits(), 2);
+
+        concurrency.on_congestion();
+        concurrency.on_congestion();
+        assert_eq!(concurrency.limit(), 1);
+        assert_eq!(concurrency.semaphore.available_permits(), 1);
+    }
+
+    #[test]
+    fn test_circuit_breaker_waits_for_min_samples() {
+        let breaker = CircuitBreaker::shared(0.5, 10, 4);
+        for _ in 0..3 {
+            breaker.record(true);
+        }
+        assert!(!breaker.is_open());
+
+        breaker.record(true);
+        assert!(breaker.is_open());
+        assert_eq!(breaker.error_rate(), (1.0, 4));
+    }
+
+    #[test]
+    fn test_circuit_breaker_uses_a_sliding_window() {
+        let breaker = CircuitBreaker::shared(0.5, 4, 4);
+        // Os erros antigos saem da janela antes de a taxa passar do limite
+        for failed in [true, true, false, false, false, true, false, true] {
+            breaker.record(failed);
+            assert!(!breaker.is_open());
+        }
+        assert_eq!(breaker.error_rate(), (0.5, 4));
+
+        breaker.record(true);
+        assert!(breaker.is_open());
+
+        // Uma vez aberto, continua aberto
+        for _ in 0..4 {
+            breaker.record(false);
+        }
+        assert!(breaker.is_open());
+    }
+
+    #[test]
+    fn test_hedge_budget_caps_extra_requests() {
+        let budget = HedgeBudget::shared(Duration::from_millis(500), 0.1, 2);
+        // Folga inicial, antes de qualquer requisição contada
+        assert!(budget.try_hedge());
+        assert!(budget.try_hedge());
+        assert!(!budget.try_hedge());
+
+        for _ in 0..20 {
+            budget.record_sent();
+        }
+        assert!(budget.try_hedge());
+        assert!(budget.try_hedge());
+        assert!(!budget.try_hedge());
+        assert_eq!(budget.hedged(), 4);
+    }
+
+    #[test]
+    fn test_unlimited_until_first_rate_limit() {
+        let mut limiter = RateLimiter::new(None);
+        let now = Instant::now();
+        assert_eq!(limiter.reserve(now), Duration::ZERO);
+        assert_eq!(limiter.reserve(now), Duration::ZERO);
+
+        limiter.on_rate_limited();
+        assert_eq!(limiter.rate(), INITIAL_THROTTLED_RATE);
+    }
+
+    #[test]
+    fn test_decrease_on_rate_limit_and_increase_on_success() {
+        let mut limiter = RateLimiter::new(Some(8.0));
+        assert_eq!(limiter.rate(), 8.0);
+
+        limiter.on_rate_limited();
+        limiter.on_rate_limited();
+        assert_eq!(limiter.rate(), 2.0);
+
+        for _ in 0..10 {
+            limiter.on_success();
+        }
+        assert!((limiter.rate() - 3.0).abs() < 1e-9);
+
+        for _ in 0..1000 {
+            limiter.on_success();
+        }
+        assert_eq!(limiter.rate(), 8.0);
+    }
+
+    #[test]
+    fn test_rate_never_drops_below_minimum() {
+        let mut limiter = RateLimiter::new(Some(4.0));
+        for _ in 0..20 {
+            limiter.on_rate_limited();
+        }
+        assert_eq!(limiter.rate(), MIN_RATE);
+    }
+
+    #[test]
+    fn test_reserve_spaces_requests_by_current_rate() {
+        let mut limiter = RateLimiter::new(Some(4.0));
+        let now = Instant::now();
+        assert_eq!(limiter.reserve(now), Duration::ZERO);
+        assert_eq!(limiter.reserve(now), Duration::from_millis(250));
+        assert_eq!(limiter.reserve(now), Duration::from_millis(500));
+
+        // Um horário já passado não acumula espera
+        let later = now + Duration::from_secs(5);
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+    }
+}
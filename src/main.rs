@@ -1,262 +1,2292 @@
-use anyhow::{Context, Result};
-use clap::Parser;
-use futures::stream::{self, StreamExt};
-use indicatif::{ProgressBar, ProgressStyle};
-use reqwest::Client;
-use serde::Deserialize;
-use std::fs::File;
-use std::io::{BufWriter, Write};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use anyhow::{bail, Context, Result};
+use clap::parser::ValueSource;
+use clap::{Arg, ArgGroup, ArgMatches, Command, CommandFactory, FromArgMatches, Parser, ValueEnum};
+use futures::StreamExt;
+use indicatif::style::ProgressTracker;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
+use owo_colors::{AnsiColors, OwoColorize};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use registrobr_finder::backend::{
+    parse_base_url, CustomUrl, AVAIL_STATUS_LEGEND, PUBLICATION_STATUS_LEGEND, RELEASE_STATUS,
+    UNKNOWN_STATUS,
+};
+use registrobr_finder::cache::ResultCache;
+use registrobr_finder::connections::ConnectionCounter;
+use registrobr_finder::dns::DnsPrefilter;
+use registrobr_finder::generator::{
+    Combinations, MAX_LABEL_LEN, MIN_LABEL_LEN, PORTUGUESE_FREQUENCY,
+};
+use registrobr_finder::history::History;
+use registrobr_finder::input::{normalize_exclusions, normalize_targets, parse_domain_list};
+use registrobr_finder::limiter::{
+    AdaptiveConcurrency, CircuitBreaker, HedgeBudget, RateLimiter, BREAKER_MIN_SAMPLES,
+    BREAKER_WINDOW, HEDGE_BURST, HEDGE_RATIO,
+};
+use registrobr_finder::metrics::{self, Metrics};
+use registrobr_finder::output::{
+    append_csv, parse_json_results, parse_yaml_results, write_csv, write_json, write_json_report,
+    write_ndjson_line, write_table, write_yaml_report, JsonReport,
+};
+use registrobr_finder::stats::{LatencyStats, RecentRate, StatusBreakdown};
+use registrobr_finder::suffixes::{
+    known_suffixes, normalize_suffix, parse_suffix_list, SuffixCategory,
+};
+use registrobr_finder::webhook::{WebhookNotifier, DEFAULT_BATCH_WINDOW};
+use registrobr_finder::{
+    build_charset, generate_combinations, generate_pronounceable, mutate_with_digits, AvailBackend,
+    CancellationToken, Checker, DomainResult, LabelFilter, OnCancel, Pattern, RdapBackend,
+    RetryPolicy, StatusKind, DNS_REGISTERED_STATUS, PROBE_DOMAIN, USER_AGENT_POOL,
+};
+use reqwest::{Client, Proxy};
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, IsTerminal, Read, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
 
-const AVAIL_API_URL: &str = "https://registro.br/v2/ajax/avail/raw/";
+mod tui;
+
+use tui::Dashboard;
+
+const DEFAULT_LENGTH: u8 = 2;
+/// Arquivo de configuração procurado no diretório atual quando --config não é informado
+const DEFAULT_CONFIG_FILE: &str = "registrobr-finder.toml";
+/// Padrão de --confirm-above
+const DEFAULT_CONFIRM_ABOVE: u64 = 100_000;
+/// Padrão de --confirm-runtime, em segundos
+const DEFAULT_CONFIRM_RUNTIME: u64 = 600;
+/// Latência típica de uma consulta ao Registro.br, usada na estimativa de duração
+const ESTIMATED_LATENCY: Duration = Duration::from_millis(500);
+/// Padrão de --pool-idle-timeout, em segundos
+const DEFAULT_POOL_IDLE_TIMEOUT: u64 = 90;
+/// Janela de vazão usada na estimativa de tempo restante da barra de progresso
+const ETA_WINDOW: Duration = Duration::from_secs(30);
+/// Requisições paralelas no início de uma varredura com --workers auto
+const INITIAL_AUTO_WORKERS: usize = 4;
+/// Até quantos domínios informados pelo usuário a saída é resumida a uma linha por
+/// domínio, sem barra de progresso nem RESUMO
+const CONCISE_MAX_DOMAINS: u64 = 5;
 
 #[derive(Parser, Debug)]
 #[command(name = "registrobr-finder")]
-#[command(about = "Verifica disponibilidade de domínios .BR via RDAP")]
+#[command(about = "Verifica disponibilidade de domínios .BR na API do Registro.br")]
+#[command(group(ArgGroup::new("randomized").args(["shuffle", "sample_rate"]).multiple(true)))]
 struct Args {
-    /// Número de caracteres (2 ou 3)
-    #[arg(short, long, default_value_t = 2)]
-    digits: u8,
+    /// Número de caracteres (atalho para --min-length e --max-length iguais)
+    #[arg(short, long, conflicts_with_all = ["min_length", "max_length"])]
+    digits: Option<u8>,
 
-    /// Número de requisições paralelas
-    #[arg(short, long, default_value_t = 20)]
-    workers: usize,
+    /// Tamanho mínimo das combinações geradas
+    #[arg(long)]
+    min_length: Option<u8>,
+
+    /// Tamanho máximo das combinações geradas
+    #[arg(long)]
+    max_length: Option<u8>,
+
+    /// Número de requisições paralelas, ou "auto" para ajustar pelas respostas
+    #[arg(short, long, default_value = "20", env = "REGISTROBR_WORKERS")]
+    workers: Workers,
 
-    /// Timeout por requisição em segundos
-    #[arg(short, long, default_value_t = 10)]
+    /// Teto de requisições paralelas com --workers auto
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 50,
+        env = "REGISTROBR_MAX_WORKERS"
+    )]
+    max_workers: usize,
+
+    /// Timeout por requisição em segundos, do início da conexão ao fim da resposta
+    #[arg(short, long, default_value_t = 10, env = "REGISTROBR_TIMEOUT")]
     timeout: u64,
 
-    /// Sufixo do domínio
-    #[arg(short, long, default_value = ".com.br")]
-    suffix: String,
+    /// Timeout só para estabelecer a conexão, em segundos (padrão: o de --timeout)
+    #[arg(long, value_name = "SECS")]
+    connect_timeout: Option<u64>,
+
+    /// Versão do HTTP: auto (negociada no TLS), 1.1 ou 2 (sem negociação)
+    #[arg(long, value_enum, default_value_t = HttpVersion::Auto)]
+    http_version: HttpVersion,
+
+    /// Conexões ociosas mantidas no pool para reaproveitamento (padrão: o número de workers)
+    #[arg(long, value_name = "N")]
+    pool_max_idle: Option<usize>,
+
+    /// Tempo em segundos que uma conexão ociosa fica no pool antes de ser fechada
+    #[arg(long, value_name = "SECS", default_value_t = DEFAULT_POOL_IDLE_TIMEOUT)]
+    pool_idle_timeout: u64,
+
+    /// Sufixo(s) do domínio, separados por vírgula ou com a opção repetida
+    #[arg(short, long, value_delimiter = ',', default_value = ".com.br", value_parser = suffix, env = "REGISTROBR_SUFFIX")]
+    suffix: Vec<String>,
+
+    /// Verifica cada nome em todos os sufixos conhecidos do .br
+    #[arg(long, conflicts_with = "suffix")]
+    suffix_all: bool,
+
+    /// Verifica cada nome nos sufixos conhecidos destas categorias (pode ser repetido)
+    #[arg(long, value_enum, value_delimiter = ',', conflicts_with_all = ["suffix", "suffix_all"])]
+    suffix_category: Vec<SuffixCategoryArg>,
+
+    /// Arquivo com os sufixos a verificar, um por linha
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["suffix", "suffix_all", "suffix_category"])]
+    suffix_file: Option<String>,
+
+    /// Apenas letras (sem números)
+    #[arg(long)]
+    letters: bool,
+
+    /// Apenas números (sem letras)
+    #[arg(long, conflicts_with = "letters")]
+    numbers: bool,
+
+    /// Não gera nomes que começam com 0 (10-99 em vez de 00-99)
+    #[arg(long, conflicts_with_all = ["check", "input", "wordlist"])]
+    no_leading_zeros: bool,
+
+    /// Conjunto de caracteres personalizado (ignora --letters e --numbers)
+    #[arg(long)]
+    charset: Option<String>,
+
+    /// Inclui hífen nas combinações (nunca no início, no fim ou duplicado)
+    #[arg(long)]
+    allow_hyphens: bool,
+
+    /// Gera nomes pronunciáveis, alternando consoantes e vogais (ex.: bel, tiko)
+    #[arg(
+        long,
+        conflicts_with_all = ["letters", "numbers", "charset", "allow_hyphens", "check", "input", "wordlist"]
+    )]
+    pronounceable: bool,
+
+    /// Só gera combinações que começam com um desses caracteres (ex.: aeiou)
+    #[arg(long, value_name = "SET", value_parser = position_chars, conflicts_with_all = ["check", "input", "wordlist"])]
+    first_char: Option<String>,
+
+    /// Só gera combinações que terminam com um desses caracteres
+    #[arg(long, value_name = "SET", value_parser = position_chars, conflicts_with_all = ["check", "input", "wordlist"])]
+    last_char: Option<String>,
+
+    /// Percorre as letras da mais para a menos comum em português (a, e, o, s...)
+    #[arg(long, conflicts_with_all = ["char_order", "shuffle", "check", "input", "wordlist"])]
+    frequency_order: bool,
+
+    /// Ordem dos caracteres em cada posição (ex.: aeo); os que faltarem vêm depois
+    #[arg(long, value_name = "CHARS", value_parser = char_order, conflicts_with_all = ["shuffle", "check", "input", "wordlist"])]
+    char_order: Option<String>,
+
+    /// Modelo dos nomes gerados, com {} no lugar de cada combinação (ex.: "loja{}")
+    #[arg(long, conflicts_with_all = ["check", "input", "wordlist"])]
+    pattern: Option<String>,
+
+    /// Texto fixo antes de cada combinação (atalho para --pattern "<PREFIXO>{}")
+    #[arg(long, conflicts_with_all = ["pattern", "check", "input", "wordlist"])]
+    prefix: Option<String>,
+
+    /// Gera apenas nomes que casam com a expressão regular (ex.: "^[aeiou]")
+    #[arg(long, value_name = "REGEX", conflicts_with_all = ["check", "input", "wordlist"])]
+    r#match: Option<String>,
+
+    /// Descarta os nomes gerados que casam com a expressão regular
+    #[arg(long, value_name = "REGEX", conflicts_with_all = ["check", "input", "wordlist"])]
+    exclude: Option<String>,
+
+    /// Repete a verificação a cada N segundos, mostrando apenas mudanças de status
+    #[arg(long, value_name = "SECS", conflicts_with_all = ["resume", "cache", "tui", "dry_run"])]
+    watch: Option<u64>,
+
+    /// Verifica os domínios em ordem aleatória
+    #[arg(long)]
+    shuffle: bool,
+
+    /// Verifica só uma amostra aleatória: cada domínio é mantido com essa probabilidade
+    #[arg(long, value_name = "RATE", value_parser = sample_rate)]
+    sample_rate: Option<f64>,
+
+    /// Semente do --shuffle e do --sample-rate, para repetir a mesma ordem e amostra
+    #[arg(long, requires = "randomized")]
+    seed: Option<u64>,
+
+    /// Verifica no máximo N domínios (após a geração e os filtros)
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Para de enviar novos domínios depois de encontrar N disponíveis
+    #[arg(long, value_name = "N", conflicts_with = "watch")]
+    min_available: Option<usize>,
+
+    /// Para de enviar novos domínios depois de N segundos de varredura
+    #[arg(long, value_name = "SECS", conflicts_with = "watch")]
+    deadline: Option<u64>,
+
+    /// Arquivo para salvar domínios disponíveis
+    #[arg(short, long, env = "REGISTROBR_OUTPUT")]
+    output: Option<String>,
+
+    /// Arquivo para salvar só os domínios disponíveis, em qualquer formato
+    #[arg(long, value_name = "FILE")]
+    output_available: Option<String>,
+
+    /// Arquivo para salvar os domínios que expiram dentro de --expiring-within
+    #[arg(long, value_name = "FILE", requires = "expiring_within")]
+    output_expiring: Option<String>,
+
+    /// Compara com os resultados de uma execução anterior em JSON e mostra o que mudou
+    #[arg(long, value_name = "FILE", conflicts_with = "watch")]
+    compare: Option<String>,
+
+    /// Banco SQLite onde cada execução grava os resultados, para acompanhar o histórico
+    #[arg(long, value_name = "FILE", conflicts_with = "watch")]
+    sqlite: Option<String>,
+
+    /// Retoma uma varredura interrompida, pulando domínios já verificados
+    #[arg(long, requires = "output")]
+    resume: bool,
+
+    /// Acrescenta ao arquivo de saída em vez de sobrescrevê-lo, pulando os domínios que
+    /// já estão nele (formatos text, csv e ndjson)
+    #[arg(long, requires = "output")]
+    append: bool,
+
+    /// Inclui o status ao lado de cada domínio no arquivo de saída (formato text)
+    #[arg(long, requires = "output")]
+    with_status: bool,
+
+    /// Grava no arquivo de saída todos os resultados, não só os disponíveis
+    #[arg(long, requires = "output", conflicts_with = "only")]
+    save_all: bool,
+
+    /// Exibe e grava apenas resultados com este(s) status (pode ser repetido)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    only: Vec<StatusFilter>,
+
+    /// Ordem da lista final e do arquivo de saída
+    #[arg(long, value_enum, default_value_t = SortKey::Name)]
+    sort: SortKey,
+
+    /// Formato da saída
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text, env = "REGISTROBR_FORMAT")]
+    format: OutputFormat,
+
+    /// Com --format json ou json-array, indenta o JSON para leitura
+    #[arg(long)]
+    json_pretty: bool,
+
+    /// Verificar domínio(s) específico(s), separados por vírgula
+    #[arg(short, long)]
+    check: Option<String>,
+
+    /// Arquivo com domínios a verificar, um por linha ("-" para stdin)
+    #[arg(short, long, conflicts_with = "check")]
+    input: Option<String>,
+
+    /// Lista de palavras usadas como nomes no lugar das combinações, uma por linha
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["check", "input"])]
+    wordlist: Option<String>,
+
+    /// Arquivo com domínios a pular (FQDNs ou nomes simples), um por linha
+    #[arg(long, value_name = "FILE")]
+    exclude_file: Option<String>,
+
+    /// Com --wordlist, também verifica cada palavra seguida de um dígito (0-9)
+    #[arg(long, requires = "wordlist")]
+    mutate: bool,
+
+    /// Número de novas tentativas em falhas de rede ou rate limiting
+    #[arg(long, default_value_t = 3, env = "REGISTROBR_RETRIES")]
+    retries: u32,
+
+    /// Atraso base entre tentativas em milissegundos (dobra a cada tentativa)
+    #[arg(long, default_value_t = 500, env = "REGISTROBR_RETRY_DELAY")]
+    retry_delay: u64,
+
+    /// Também tenta de novo respostas que não são o JSON esperado (ex.: página de erro HTML)
+    #[arg(long)]
+    retry_on_parse_error: bool,
+
+    /// Espera em milissegundos antes de cada requisição, em cada worker
+    #[arg(long, value_name = "MS", default_value = "0", env = "REGISTROBR_DELAY")]
+    delay: u64,
+
+    /// User-Agent enviado nas consultas
+    #[arg(long, value_name = "STR", env = "REGISTROBR_USER_AGENT")]
+    user_agent: Option<String>,
+
+    /// Alterna a cada requisição entre User-Agents de navegadores comuns
+    #[arg(long, conflicts_with = "user_agent")]
+    rotate_user_agents: bool,
+
+    /// Segue redirecionamentos da API; sem a opção, eles viram erro no resultado
+    #[arg(long)]
+    follow_redirects: bool,
+
+    /// Guarda os cookies recebidos e os reenvia nas consultas seguintes (só em memória)
+    #[arg(long)]
+    cookies: bool,
+
+    /// Limite de requisições por segundo (a taxa também é reduzida ao receber 429)
+    #[arg(long, env = "REGISTROBR_MAX_RPS")]
+    max_rps: Option<f64>,
+
+    /// Proxy para as requisições (http://, https:// ou socks5://, com usuário:senha opcional)
+    #[arg(long, env = "REGISTROBR_PROXY")]
+    proxy: Option<String>,
+
+    /// API consultada: o endpoint de disponibilidade do site ou o servidor RDAP
+    #[arg(long, value_enum, default_value_t = BackendKind::Avail, env = "REGISTROBR_BACKEND")]
+    backend: BackendKind,
+
+    /// Endereço base da API no lugar do oficial (espelho, servidor de testes); o FQDN é
+    /// acrescentado ao fim do caminho
+    #[arg(long, value_name = "URL", value_parser = api_url, env = "REGISTROBR_API_URL")]
+    api_url: Option<String>,
+
+    /// Consulta o DNS antes da API: domínios que resolvem são marcados como registrados
+    #[arg(long)]
+    dns_prefilter: bool,
+
+    /// URL que recebe um POST JSON a cada domínio disponível encontrado
+    #[arg(long, env = "REGISTROBR_WEBHOOK")]
+    webhook: Option<String>,
+
+    /// Serve métricas no formato do Prometheus em http://ADDR:PORT/metrics
+    #[arg(long, value_name = "PORT", env = "REGISTROBR_METRICS_PORT")]
+    metrics_port: Option<u16>,
+
+    /// Endereço em que o servidor de métricas escuta (0.0.0.0 para todas as interfaces)
+    #[arg(
+        long,
+        value_name = "ADDR",
+        env = "REGISTROBR_METRICS_ADDR",
+        default_value = "127.0.0.1",
+        requires = "metrics_port"
+    )]
+    metrics_addr: IpAddr,
+
+    /// Arquivo JSON de cache de resultados
+    #[arg(long, env = "REGISTROBR_CACHE")]
+    cache: Option<String>,
+
+    /// Validade do cache em segundos (domínios não disponíveis valem 7x mais)
+    #[arg(long, default_value_t = 86400, env = "REGISTROBR_CACHE_TTL")]
+    cache_ttl: u64,
+
+    /// Sai com código 2 se houver mais de N erros
+    #[arg(long, value_name = "N")]
+    max_errors: Option<usize>,
+
+    /// Sai com código 2 se qualquer verificação falhar (equivale a --max-errors 0)
+    #[arg(long, conflicts_with = "max_errors")]
+    fail_on_error: bool,
+
+    /// Aborta a varredura se a fração de erros nas últimas consultas passar de RATE (0 a 1)
+    #[arg(long, value_name = "RATE", conflicts_with = "watch")]
+    max_error_rate: Option<f64>,
+
+    /// Envia uma requisição de reserva quando a resposta demora mais de MS milissegundos
+    #[arg(long, value_name = "MS")]
+    hedge_after: Option<u64>,
+
+    /// Lista no resumo os domínios registrados que expiram em até N dias
+    #[arg(long, value_name = "DAYS")]
+    expiring_within: Option<i64>,
+
+    /// Inclui no resumo a latência das consultas (mín/mediana/p95/máx) e a taxa média
+    #[arg(long)]
+    stats: bool,
+
+    /// Quantidade de domínios acima da qual a varredura exige --yes
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONFIRM_ABOVE)]
+    confirm_above: u64,
+
+    /// Duração estimada, em segundos, acima da qual a varredura pede confirmação
+    #[arg(long, value_name = "SECS", default_value_t = DEFAULT_CONFIRM_RUNTIME)]
+    confirm_runtime: u64,
+
+    /// Confirma varreduras maiores que --confirm-above ou --confirm-runtime sem perguntar
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Fora de um terminal, intervalo em segundos entre as linhas de progresso (0 desativa)
+    #[arg(long, value_name = "SECS", default_value = "10")]
+    progress_interval: u64,
+
+    /// Não desenha a barra de progresso, mesmo em um terminal
+    #[arg(long)]
+    no_progress: bool,
+
+    /// Lista os domínios que seriam verificados, sem fazer requisições
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Grava os domínios que seriam verificados em FILE, um por linha, e sai sem consultar
+    /// a API ("-" para o stdout)
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["dry_run", "count_only", "watch", "resume", "tui"])]
+    combinations_file: Option<String>,
+
+    /// Não faz a consulta de teste a registro.com.br antes da varredura
+    #[arg(long)]
+    skip_probe: bool,
+
+    /// Só mostra quantos domínios seriam verificados, sem gerar a lista, e sai
+    #[arg(long, conflicts_with_all = ["dry_run", "watch"])]
+    count_only: bool,
+
+    /// Explica os códigos de status da API e o publication-status, e sai
+    #[arg(long)]
+    explain: bool,
+
+    /// Mostra todos os domínios verificados
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Mostra, abaixo de cada resultado exibido, o corpo bruto da resposta da API
+    #[arg(long)]
+    raw: bool,
+
+    /// Mostra apenas os domínios disponíveis, um por linha, sem banner, progresso
+    /// nem resumo
+    #[arg(short, long, conflicts_with_all = ["verbose", "tui", "watch"])]
+    quiet: bool,
+
+    /// Painel interativo no terminal no lugar da barra de progresso
+    #[arg(long)]
+    tui: bool,
+
+    /// Nível dos logs de diagnóstico, gravados no stderr
+    #[arg(long, value_enum, default_value_t = LogLevel::Off, env = "REGISTROBR_LOG_LEVEL")]
+    log_level: LogLevel,
+
+    /// Cores nas linhas de resultado: auto (só em terminal e sem NO_COLOR), always ou never
+    #[arg(long, value_enum, default_value_t = ColorMode::Auto)]
+    color: ColorMode,
+
+    /// Arquivo TOML com valores para as opções (padrão: ./registrobr-finder.toml, se existir)
+    #[arg(long, env = "REGISTROBR_CONFIG")]
+    config: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Lista de domínios disponíveis, um por linha
+    Text,
+    /// Objeto JSON com todos os resultados e os totais da varredura
+    Json,
+    /// Todos os resultados como um array JSON, sem os totais
+    JsonArray,
+    /// Todos os resultados em CSV (domain,available,status,error,publication_status,unicode,expires_in_days,restriction,status_code)
+    Csv,
+    /// Um objeto JSON por linha, escrito assim que cada resultado fica pronto
+    Ndjson,
+    /// Tabela com bordas (domínio, status e expiração) de todos os resultados, ao final
+    Table,
+    /// Documento YAML com todos os resultados e os totais da varredura
+    Yaml,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum StatusFilter {
+    /// Disponível (status 0)
+    Available,
+    /// Registrado (status 2)
+    Registered,
+    /// Em processo (status 3), fora do processo de liberação
+    InProcess,
+    /// Em processo de liberação (status 3 com publication-status de liberação)
+    Release,
+    /// Indisponível (status 4)
+    Unavailable,
+    /// Código de status que a API não documenta
+    Unknown,
+    /// Falha na verificação
+    Error,
+}
+
+/// Valor de --workers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Workers {
+    Fixed(usize),
+    /// Concorrência ajustada pelas respostas, até --max-workers
+    Auto,
+}
+
+impl FromStr for Workers {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "auto" {
+            return Ok(Self::Auto);
+        }
+        match value.parse() {
+            Ok(0) => Err("deve ser maior que zero".to_string()),
+            Ok(workers) => Ok(Self::Fixed(workers)),
+            Err(_) => Err(format!("{:?} não é um número nem \"auto\"", value)),
+        }
+    }
+}
+
+/// Valida --sample-rate já na leitura dos argumentos
+fn sample_rate(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(rate) if (0.0..=1.0).contains(&rate) => Ok(rate),
+        _ => Err("deve ser um número entre 0 e 1 (ex.: 0.1)".to_string()),
+    }
+}
+
+/// Valida --first-char e --last-char: letras e números, sem hífen (que nunca abre
+/// nem fecha um nome)
+fn position_chars(value: &str) -> Result<String, String> {
+    let set = value.to_lowercase();
+    if set.is_empty()
+        || !set
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        return Err("deve conter só letras de a a z e dígitos (ex.: aeiou)".to_string());
+    }
+    Ok(set)
+}
+
+/// Valida --char-order: letras, dígitos e hífen, sem repetição
+fn char_order(value: &str) -> Result<String, String> {
+    let order = value.to_lowercase();
+    if order.is_empty()
+        || !order
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err("deve conter só letras de a a z, dígitos e hífen (ex.: aeo)".to_string());
+    }
+    if let Some(repeated) = order
+        .char_indices()
+        .find(|&(i, c)| order[..i].contains(c))
+        .map(|(_, c)| c)
+    {
+        return Err(format!(
+            "o caractere {:?} aparece mais de uma vez",
+            repeated
+        ));
+    }
+    Ok(order)
+}
+
+/// Normaliza e valida cada --suffix já na leitura dos argumentos
+fn suffix(value: &str) -> Result<String, String> {
+    normalize_suffix(value).map_err(|e| e.to_string())
+}
+
+/// Valida --api-url já na leitura dos argumentos
+fn api_url(value: &str) -> Result<String, String> {
+    parse_base_url(value).map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SuffixCategoryArg {
+    /// .com.br, .net.br, .app.br...
+    Generic,
+    /// .ind.br, .inf.br, .tur.br...
+    Business,
+    /// .adv.br, .eng.br, .med.br...
+    Professional,
+    /// .blog.br, .nom.br...
+    Personal,
+    /// .rio.br, .sampa.br, .poa.br...
+    City,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SortKey {
+    /// Ordem alfabética
+    Name,
+    /// Mais curtos primeiro
+    Length,
+    /// Expiração mais próxima primeiro (domínios sem data ficam no fim)
+    Expiry,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum BackendKind {
+    /// Endpoint de disponibilidade usado pelo site do Registro.br
+    Avail,
+    /// Servidor RDAP oficial (rdap.registro.br)
+    Rdap,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum HttpVersion {
+    Auto,
+    #[value(name = "1.1")]
+    Http1,
+    #[value(name = "2")]
+    Http2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// Decide se as cores são usadas; no modo auto, só em terminal e sem a variável
+    /// NO_COLOR (https://no-color.org)
+    fn enabled(self, is_terminal: bool, no_color: bool) -> bool {
+        match self {
+            ColorMode::Auto => is_terminal && !no_color,
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
+}
+
+/// Aplica `color` a `text` quando as cores estão ativadas
+fn paint(text: &str, color: AnsiColors, enabled: bool) -> String {
+    if enabled {
+        text.color(color).to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => LevelFilter::OFF,
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Destino dos logs: o stderr, com a barra de progresso suspensa durante a escrita
+/// para que as linhas não se misturem
+#[derive(Clone)]
+struct ProgressWriter(ProgressBar);
+
+impl Write for ProgressWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.suspend(|| io::stderr().write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for ProgressWriter {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// Campo `{recent_eta}` da barra de progresso: tempo restante pela vazão dos últimos
+/// `ETA_WINDOW`. O `{eta}` do indicatif usa uma média que demora a reagir quando o
+/// limitador adaptativo reduz ou recupera o ritmo.
+#[derive(Clone)]
+struct RecentEta(RecentRate);
+
+impl RecentEta {
+    fn new() -> Self {
+        Self(RecentRate::new(ETA_WINDOW))
+    }
+}
+
+impl ProgressTracker for RecentEta {
+    fn clone_box(&self) -> Box<dyn ProgressTracker> {
+        Box::new(self.clone())
+    }
+
+    fn tick(&mut self, state: &ProgressState, now: Instant) {
+        self.0.record(now, state.pos());
+    }
+
+    fn reset(&mut self, _: &ProgressState, _: Instant) {
+        *self = Self::new();
+    }
+
+    fn write(&self, state: &ProgressState, w: &mut dyn fmt::Write) {
+        let remaining = state.len().unwrap_or(0).saturating_sub(state.pos());
+        let _ = match self.0.eta(remaining) {
+            Some(eta) => write!(w, "~{}", format_runtime(eta)),
+            None => write!(w, "-"),
+        };
+    }
+}
+
+/// Lista os sufixos para o banner, abreviada quando são muitos
+fn describe_suffixes(suffixes: &[String]) -> String {
+    const SHOWN: usize = 3;
+    if suffixes.len() <= SHOWN {
+        suffixes.join(", ")
+    } else {
+        format!(
+            "{}, ... ({} sufixos)",
+            suffixes[..SHOWN].join(", "),
+            suffixes.len()
+        )
+    }
+}
+
+/// Mostra uma linha no stderr acima da barra de progresso. Ao contrário de
+/// `ProgressBar::println`, a linha aparece mesmo com a barra oculta (stderr fora de um
+/// terminal).
+fn print_above(progress: &ProgressBar, line: impl AsRef<str>) {
+    progress.suspend(|| eprintln!("{}", line.as_ref()));
+}
+
+/// Duração estimada de `total` consultas com `workers` em paralelo, cada uma levando
+/// `latency` mais o `delay` configurado; com --max-rps, a taxa máxima também limita
+fn estimate_runtime(
+    total: u64,
+    workers: usize,
+    latency: Duration,
+    delay: Duration,
+    max_rps: Option<f64>,
+) -> Duration {
+    let rounds = total.div_ceil(workers.max(1) as u64);
+    let parallel = (latency + delay).saturating_mul(rounds.try_into().unwrap_or(u32::MAX));
+    match max_rps.filter(|rps| *rps > 0.0) {
+        Some(rps) => parallel.max(Duration::from_secs_f64(total as f64 / rps)),
+        None => parallel,
+    }
+}
+
+/// Duração aproximada para leitura humana: `45s`, `19min 26s`, `2h 05min`
+fn format_runtime(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}min {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}min", secs / 3600, secs / 60 % 60),
+    }
+}
+
+/// Pergunta no terminal se a varredura deve continuar. Sem um terminal para
+/// perguntar, retorna `None`
+fn ask_confirmation(question: &str) -> Result<Option<bool>> {
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return Ok(None);
+    }
+    eprint!("{} [s/N] ", question);
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    let answer = answer.trim().to_lowercase();
+    Ok(Some(matches!(answer.as_str(), "s" | "sim" | "y" | "yes")))
+}
+
+/// Ordena os resultados pela chave escolhida, desempatando pelo nome
+fn sort_results(results: &mut [DomainResult], key: SortKey) {
+    results.sort_by(|a, b| {
+        let primary = match key {
+            SortKey::Name => CmpOrdering::Equal,
+            SortKey::Length => a.domain.len().cmp(&b.domain.len()),
+            SortKey::Expiry => match (a.expiry_date(), b.expiry_date()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => CmpOrdering::Less,
+                (None, Some(_)) => CmpOrdering::Greater,
+                (None, None) => CmpOrdering::Equal,
+            },
+        };
+        primary.then_with(|| a.domain.cmp(&b.domain))
+    });
+}
+
+impl From<StatusFilter> for StatusKind {
+    fn from(filter: StatusFilter) -> Self {
+        match filter {
+            StatusFilter::Available => StatusKind::Available,
+            StatusFilter::Registered => StatusKind::Registered,
+            StatusFilter::InProcess => StatusKind::InProcess,
+            StatusFilter::Release => StatusKind::Release,
+            StatusFilter::Unavailable => StatusKind::Unavailable,
+            StatusFilter::Unknown => StatusKind::Unknown,
+            StatusFilter::Error => StatusKind::Error,
+        }
+    }
+}
+
+impl From<SuffixCategoryArg> for SuffixCategory {
+    fn from(category: SuffixCategoryArg) -> Self {
+        match category {
+            SuffixCategoryArg::Generic => SuffixCategory::Generic,
+            SuffixCategoryArg::Business => SuffixCategory::Business,
+            SuffixCategoryArg::Professional => SuffixCategory::Professional,
+            SuffixCategoryArg::Personal => SuffixCategory::Personal,
+            SuffixCategoryArg::City => SuffixCategory::City,
+        }
+    }
+}
+
+impl Args {
+    /// Intervalo de tamanhos a gerar, derivado de --digits ou --min-length/--max-length
+    fn length_range(&self) -> Result<RangeInclusive<u8>> {
+        let (min, max) = match self.digits {
+            Some(digits) => (digits, digits),
+            None => {
+                let min = self.min_length.unwrap_or(DEFAULT_LENGTH);
+                (min, self.max_length.unwrap_or(min))
+            }
+        };
+
+        if min == 0 {
+            bail!("O tamanho mínimo deve ser maior que zero");
+        }
+        if min > max {
+            bail!("Tamanho mínimo ({}) maior que o máximo ({})", min, max);
+        }
+        // Com --pattern ou --prefix, quem confere o tamanho do nome completo é
+        // Pattern::check_lengths
+        let whole_name = self.pattern.is_none() && self.prefix.is_none();
+        if whole_name && ((min as usize) < MIN_LABEL_LEN || (max as usize) > MAX_LABEL_LEN) {
+            bail!(
+                "Tamanhos de {} a {} fora do permitido para um nome .br ({} a {})",
+                min,
+                max,
+                MIN_LABEL_LEN,
+                MAX_LABEL_LEN
+            );
+        }
+
+        Ok(min..=max)
+    }
+}
+
+/// Interpreta a linha de comando completando-a com o arquivo de configuração.
+/// Precedência: linha de comando > arquivo de configuração > padrões.
+fn parse_args<I, T>(cli: I) -> Result<Args>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString>,
+{
+    let cli: Vec<OsString> = cli.into_iter().map(Into::into).collect();
+    let matches = Args::command().get_matches_from(&cli);
+
+    let path = match matches.get_one::<String>("config") {
+        Some(path) => path.clone(),
+        None if Path::new(DEFAULT_CONFIG_FILE).exists() => DEFAULT_CONFIG_FILE.to_string(),
+        None => return args_from_matches(&matches),
+    };
+
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Falha ao ler arquivo {}", path))?;
+    let table: toml::Table = toml::from_str(&content)
+        .with_context(|| format!("Falha ao interpretar {} como TOML", path))?;
+
+    // Os valores do arquivo viram opções inseridas antes das informadas pelo usuário,
+    // deixando a validação (tipos, conflitos) por conta do próprio clap
+    let command = Args::command();
+    let mut from_file = Vec::new();
+    for (key, value) in &table {
+        let id = key.replace('-', "_");
+        if id == "config"
+            || !command
+                .get_arguments()
+                .any(|arg| arg.get_id() == id.as_str())
+        {
+            bail!("Opção desconhecida em {}: {}", path, key);
+        }
+        // Linha de comando e variáveis de ambiente têm precedência sobre o arquivo,
+        // inclusive quando informam uma opção incompatível com esta
+        if given_by_user(&matches, &id) || conflicts_with_user_args(&command, &matches, &id) {
+            continue;
+        }
+        from_file.extend(config_value_args(&id.replace('_', "-"), value)?);
+    }
+
+    let mut argv = cli;
+    argv.splice(1..1, from_file.into_iter().map(OsString::from));
+    args_from_matches(&Args::command().get_matches_from(argv))
+}
+
+/// Indica se a opção `id` veio da linha de comando ou de uma variável de ambiente
+fn given_by_user(matches: &ArgMatches, id: &str) -> bool {
+    matches!(
+        matches.value_source(id),
+        Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+    )
+}
+
+/// Indica se a opção `id` conflita com alguma informada pelo usuário, seja pelo
+/// `conflicts_with` de uma das duas, seja por um grupo exclusivo em comum
+fn conflicts_with_user_args(command: &Command, matches: &ArgMatches, id: &str) -> bool {
+    let Some(arg) = command.get_arguments().find(|arg| arg.get_id() == id) else {
+        return false;
+    };
+    let conflicts = |arg: &Arg, other: &Arg| {
+        command
+            .get_arg_conflicts_with(arg)
+            .iter()
+            .any(|conflict| conflict.get_id() == other.get_id())
+    };
+    let by_args = command
+        .get_arguments()
+        .filter(|other| given_by_user(matches, other.get_id().as_str()))
+        .any(|other| conflicts(arg, other) || conflicts(other, arg));
+    let by_groups = command
+        .get_groups()
+        .filter(|group| !ArgGroup::clone(group).is_multiple() && group.get_args().any(|a| a == id))
+        .any(|group| {
+            group
+                .get_args()
+                .any(|other| other != id && given_by_user(matches, other.as_str()))
+        });
+    by_args || by_groups
+}
+
+/// Monta os argumentos e, sem --format explícito, deduz o formato pela extensão do
+/// --output
+fn args_from_matches(matches: &ArgMatches) -> Result<Args> {
+    let mut args = Args::from_arg_matches(matches)?;
+    if matches.value_source("format") == Some(ValueSource::DefaultValue) {
+        if let Some(format) = args.output.as_deref().and_then(format_from_extension) {
+            args.format = format;
+        }
+    }
+    Ok(args)
+}
+
+/// Formato correspondente à extensão de `path`, se for uma das conhecidas
+fn format_from_extension(path: &str) -> Option<OutputFormat> {
+    let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+    match extension.as_str() {
+        "txt" => Some(OutputFormat::Text),
+        "json" => Some(OutputFormat::Json),
+        "csv" => Some(OutputFormat::Csv),
+        "ndjson" | "jsonl" => Some(OutputFormat::Ndjson),
+        "yaml" | "yml" => Some(OutputFormat::Yaml),
+        _ => None,
+    }
+}
+
+/// Converte um valor do arquivo de configuração nos argumentos equivalentes
+fn config_value_args(flag: &str, value: &toml::Value) -> Result<Vec<String>> {
+    let args = match value {
+        toml::Value::Boolean(true) => vec![format!("--{}", flag)],
+        toml::Value::Boolean(false) => Vec::new(),
+        toml::Value::String(s) => vec![format!("--{}={}", flag, s)],
+        toml::Value::Integer(n) => vec![format!("--{}={}", flag, n)],
+        toml::Value::Float(n) => vec![format!("--{}={}", flag, n)],
+        toml::Value::Array(items) => items
+            .iter()
+            .map(|item| config_value_args(flag, item))
+            .collect::<Result<Vec<_>>>()?
+            .concat(),
+        _ => bail!(
+            "Valor inválido para a opção {} no arquivo de configuração",
+            flag
+        ),
+    };
+    Ok(args)
+}
+
+/// Embaralha `items`, de forma reproduzível quando há `seed`
+fn shuffle<T>(items: &mut [T], seed: Option<u64>) {
+    match seed {
+        Some(seed) => items.shuffle(&mut StdRng::seed_from_u64(seed)),
+        None => items.shuffle(&mut rand::rng()),
+    }
+}
+
+/// Mantém cada item com probabilidade `rate`, sem materializar a sequência
+fn sample<I: Iterator>(items: I, rate: f64, seed: Option<u64>) -> impl Iterator<Item = I::Item> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+    items.filter(move |_| rng.random_bool(rate))
+}
+
+/// Código de saída quando ao menos um domínio disponível foi encontrado
+const EXIT_FOUND: u8 = 0;
+/// Nenhum disponível, com os erros dentro do limite
+const EXIT_NONE_FOUND: u8 = 1;
+/// Erros acima do limite, todas as verificações falharam ou erro fatal
+const EXIT_ERRORS: u8 = 2;
+
+/// Código de saída de uma varredura. Erros acima de `max_errors` (ou todas as
+/// verificações com erro) têm prioridade sobre os domínios encontrados.
+fn exit_code(checked: usize, available: usize, errors: usize, max_errors: Option<usize>) -> u8 {
+    let all_failed = checked > 0 && errors == checked;
+    if all_failed || max_errors.is_some_and(|max| errors > max) {
+        EXIT_ERRORS
+    } else if available > 0 {
+        EXIT_FOUND
+    } else {
+        EXIT_NONE_FOUND
+    }
+}
+
+/// Indica se um resultado entra na saída estruturada: os status de `--only`, só os
+/// disponíveis com `--quiet` ou, sem nenhum dos dois, todos
+fn select(only: &[StatusKind], quiet: bool, result: &DomainResult) -> bool {
+    if only.is_empty() {
+        !quiet || result.available
+    } else {
+        only.contains(&result.kind())
+    }
+}
+
+/// Domínios registrados que expiram em até `days` dias (incluindo os que já
+/// expiraram), do mais próximo ao mais distante da expiração
+fn expiring_within(results: &[DomainResult], days: i64) -> Vec<&DomainResult> {
+    let mut expiring: Vec<_> = results
+        .iter()
+        .filter(|r| r.expires_in_days.is_some_and(|left| left <= days))
+        .collect();
+    expiring.sort_by_key(|r| r.expires_in_days);
+    expiring
+}
+
+/// Mudança de status de um domínio entre duas rodadas do --watch
+#[derive(Debug, PartialEq, Eq)]
+struct StatusChange {
+    domain: String,
+    /// Status na rodada anterior; `None` na primeira vez que o domínio é verificado
+    previous: Option<String>,
+    current: String,
+}
+
+/// Compara os resultados com os status da rodada anterior. Erros não contam como
+/// mudança: o domínio mantém o último status conhecido.
+fn status_changes(
+    previous: &HashMap<String, String>,
+    results: &[DomainResult],
+) -> Vec<StatusChange> {
+    results
+        .iter()
+        .filter(|r| r.error.is_none())
+        .filter_map(|r| {
+            let current = status_label(r);
+            let before = previous.get(&r.domain);
+            (before != Some(&current)).then(|| StatusChange {
+                domain: r.domain.clone(),
+                previous: before.cloned(),
+                current,
+            })
+        })
+        .collect()
+}
+
+/// Legenda do --explain: os códigos de status da API, o `publication-status` e os
+/// status que o próprio programa produz
+fn explain_statuses(out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "CÓDIGOS DE STATUS DA API DO REGISTRO.BR:")?;
+    for (code, label, meaning) in AVAIL_STATUS_LEGEND {
+        writeln!(out, "   {} = {}: {}", code, label, meaning)?;
+    }
+
+    writeln!(
+        out,
+        "\nPUBLICATION-STATUS (entre parênteses, depois do status):"
+    )?;
+    for (value, meaning) in PUBLICATION_STATUS_LEGEND {
+        writeln!(out, "   {}: {}", value, meaning)?;
+    }
+    writeln!(
+        out,
+        "   outros valores são mostrados como vieram; os que mencionam liberação ou \
+         competição contam como \"{}\"",
+        RELEASE_STATUS
+    )?;
+
+    writeln!(out, "\nOUTROS STATUS:")?;
+    writeln!(
+        out,
+        "   {}: o pré-filtro de DNS (--dns-prefilter) encontrou o domínio, sem consultar a API",
+        DNS_REGISTERED_STATUS
+    )?;
+    writeln!(
+        out,
+        "   {} (N): código N, que a API não documenta (como o 1); nunca conta como \
+         disponível e aparece à parte no resumo",
+        UNKNOWN_STATUS
+    )?;
+    writeln!(
+        out,
+        "   erro: a verificação falhou (rede, timeout, HTTP ou resposta inválida)"
+    )?;
+    Ok(())
+}
+
+/// Status comparado entre execuções: o texto da API, ou `-` quando não veio nenhum
+fn status_label(result: &DomainResult) -> String {
+    result.status.clone().unwrap_or_else(|| "-".to_string())
+}
+
+/// O que mudou em relação a uma execução anterior (--compare)
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Comparison {
+    newly_available: Vec<String>,
+    newly_registered: Vec<String>,
+    newly_erroring: Vec<String>,
+}
+
+/// Compara com `previous` usando a mesma lógica do --watch. Só contam os domínios que
+/// já estavam na execução anterior, e uma renovação (mesma categoria, outra data de
+/// expiração) não é mudança.
+fn compare_runs(previous: &[DomainResult], results: &[DomainResult]) -> Comparison {
+    let before: HashMap<&str, &DomainResult> =
+        previous.iter().map(|r| (r.domain.as_str(), r)).collect();
+    let known: HashMap<String, String> = previous
+        .iter()
+        .filter(|r| r.error.is_none())
+        .map(|r| (r.domain.clone(), status_label(r)))
+        .collect();
+    let current: HashMap<&str, &DomainResult> =
+        results.iter().map(|r| (r.domain.as_str(), r)).collect();
+
+    let mut comparison = Comparison::default();
+    for change in status_changes(&known, results) {
+        if change.previous.is_none() {
+            continue;
+        }
+        let (was, now) = (
+            before[change.domain.as_str()].kind(),
+            current[change.domain.as_str()].kind(),
+        );
+        match now {
+            _ if now == was => {}
+            StatusKind::Available => comparison.newly_available.push(change.domain),
+            StatusKind::Registered => comparison.newly_registered.push(change.domain),
+            _ => {}
+        }
+    }
+    comparison.newly_erroring = results
+        .iter()
+        .filter(|r| {
+            r.error.is_some()
+                && before
+                    .get(r.domain.as_str())
+                    .is_some_and(|b| b.error.is_none())
+        })
+        .map(|r| r.domain.clone())
+        .collect();
+
+    comparison.newly_available.sort();
+    comparison.newly_registered.sort();
+    comparison.newly_erroring.sort();
+    comparison
+}
+
+/// Espera `duration`, retornando antes se a execução for interrompida
+async fn sleep_unless_interrupted(duration: Duration, interrupted: &CancellationToken) {
+    tokio::select! {
+        _ = tokio::time::sleep(duration) => {}
+        _ = interrupted.cancelled() => {}
+    }
+}
+
+/// Modo --watch: verifica `targets` a cada `interval` até o Ctrl+C, mostrando o status
+/// inicial de cada domínio e, depois, só o que mudou
+async fn watch(
+    checker: &Checker,
+    targets: Vec<(String, String)>,
+    workers: usize,
+    interval: Duration,
+    notifier: Option<&WebhookNotifier>,
+    interrupted: &CancellationToken,
+    console: &mut dyn Write,
+) -> Result<()> {
+    writeln!(
+        console,
+        "Monitorando {} domínios a cada {}s (Ctrl+C para sair)\n",
+        targets.len(),
+        interval.as_secs()
+    )?;
+
+    let mut previous: HashMap<String, String> = HashMap::new();
+    while !interrupted.is_cancelled() {
+        let mut results: Vec<DomainResult> = checker
+            .check_stream_cancellable(
+                targets.clone(),
+                workers,
+                interrupted.clone(),
+                OnCancel::Finish,
+            )
+            .collect()
+            .await;
+        sort_results(&mut results, SortKey::Name);
+
+        let now = httpdate::fmt_http_date(SystemTime::now());
+        for change in status_changes(&previous, &results) {
+            match change.previous {
+                Some(ref before) => writeln!(
+                    console,
+                    "[{}] {}: {} -> {}",
+                    now, change.domain, before, change.current
+                )?,
+                None => writeln!(console, "[{}] {}: {}", now, change.domain, change.current)?,
+            }
+            if let (Some(notifier), Some(result)) = (
+                notifier,
+                results
+                    .iter()
+                    .find(|r| r.domain == change.domain && r.available),
+            ) {
+                notifier.notify(result);
+            }
+            previous.insert(change.domain, change.current);
+        }
+
+        sleep_unless_interrupted(interval, interrupted).await;
+    }
+
+    Ok(())
+}
+
+/// Lê o conteúdo de um arquivo de domínios, ou da entrada padrão quando `path` é "-"
+fn read_input(path: &str) -> Result<String> {
+    let mut content = String::new();
+    if path == "-" {
+        io::stdin()
+            .read_to_string(&mut content)
+            .context("Falha ao ler domínios da entrada padrão")?;
+    } else {
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut content))
+            .with_context(|| format!("Falha ao ler arquivo {}", path))?;
+    }
+    Ok(content)
+}
+
+/// Grava um domínio por linha, com o status ao lado se `with_status`. O arquivo só é
+/// criado quando há domínios; retorna se ele foi gravado.
+fn write_domain_list(
+    path: &str,
+    results: &[&DomainResult],
+    with_status: bool,
+    append: bool,
+) -> Result<bool> {
+    if results.is_empty() {
+        return Ok(false);
+    }
+
+    let mut writer = BufWriter::new(create_output_file(path, append)?);
+    for d in results {
+        if with_status {
+            match (&d.status, &d.error) {
+                (Some(status), _) => writeln!(writer, "{}  {}", d.domain, status)?,
+                (None, Some(error)) => writeln!(writer, "{}  erro ({})", d.domain, error)?,
+                (None, None) => writeln!(writer, "{}  -", d.domain)?,
+            }
+        } else {
+            writeln!(writer, "{}", d.domain)?;
+        }
+    }
+    writer.flush()?;
+    Ok(true)
+}
+
+/// Cria o arquivo de saída, ou o abre no fim com `append`
+fn create_output_file(path: &str, append: bool) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .with_context(|| format!("Falha ao criar arquivo {}", path))
+}
+
+/// Destino da saída estruturada: o arquivo de --output ou o stdout
+fn open_output(path: Option<&str>, append: bool) -> Result<Box<dyn Write>> {
+    match path {
+        Some(path) => Ok(Box::new(BufWriter::new(create_output_file(path, append)?))),
+        None => Ok(Box::new(io::stdout().lock())),
+    }
+}
+
+/// Arquivo auxiliar do --resume com todos os domínios já verificados
+fn progress_path(output: &str) -> String {
+    format!("{}.progress", output)
+}
+
+/// Lê os domínios registrados no arquivo de progresso, se existir
+fn read_progress(path: &str) -> Result<HashSet<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashSet::new()),
+        Err(e) => Err(e).with_context(|| format!("Falha ao ler arquivo {}", path)),
+    }
+}
+
+/// Lê os resultados de uma execução anterior gravados em --output, se existir
+fn read_previous_results(path: &str, format: OutputFormat) -> Result<Vec<DomainResult>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Falha ao ler arquivo {}", path)),
+    };
+
+    let results = match format {
+        // Uma linha por domínio disponível, opcionalmente seguida do status
+        OutputFormat::Text => content
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.trim().splitn(2, char::is_whitespace);
+                let domain = parts.next().filter(|d| !d.is_empty())?;
+                let status = parts.next().map(str::trim).filter(|s| !s.is_empty());
+                Some(DomainResult {
+                    domain: domain.to_string(),
+                    available: true,
+                    status: status.map(str::to_string),
+                    error: None,
+                    ..Default::default()
+                })
+            })
+            .collect(),
+        OutputFormat::Json | OutputFormat::JsonArray => parse_json_results(&content)
+            .with_context(|| format!("Falha ao interpretar {} como JSON", path))?,
+        OutputFormat::Csv => csv::Reader::from_reader(content.as_bytes())
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Falha ao interpretar {} como CSV", path))?,
+        OutputFormat::Ndjson => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Falha ao interpretar {} como NDJSON", path))?,
+        OutputFormat::Yaml => parse_yaml_results(&content)
+            .with_context(|| format!("Falha ao interpretar {} como YAML", path))?,
+        OutputFormat::Table => bail!("--resume não é compatível com --format table"),
+    };
+
+    Ok(results)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::from(EXIT_ERRORS)
+        }
+    }
+}
+
+async fn run() -> Result<ExitCode> {
+    let mut args = parse_args(std::env::args_os())?;
+    if args.explain {
+        explain_statuses(&mut io::stdout())?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    // Opções inválidas são recusadas antes de qualquer leitura de arquivo ou
+    // requisição, em todos os modos (inclusive --dry-run e --count-only)
+    if matches!(args.max_rps, Some(rps) if rps <= 0.0) {
+        bail!("--max-rps deve ser maior que zero");
+    }
+    if matches!(args.max_error_rate, Some(rate) if !(0.0..=1.0).contains(&rate)) {
+        bail!("--max-error-rate deve estar entre 0 e 1 (ex.: 0.5)");
+    }
+    if args.json_pretty && !matches!(args.format, OutputFormat::Json | OutputFormat::JsonArray) {
+        bail!("--json-pretty só vale com --format json ou json-array");
+    }
+    if args.hedge_after == Some(0) {
+        bail!("--hedge-after deve ser maior que zero");
+    }
+    if args.resume && args.format == OutputFormat::Table {
+        bail!("--resume não é compatível com --format table");
+    }
+    // Um documento JSON ou uma tabela não podem ser estendidos acrescentando bytes
+    if args.append
+        && !matches!(
+            args.format,
+            OutputFormat::Text | OutputFormat::Csv | OutputFormat::Ndjson
+        )
+    {
+        bail!("--append só funciona com os formatos text, csv e ndjson");
+    }
+    if args.min_available == Some(0) {
+        bail!("--min-available deve ser maior que zero");
+    }
+    if args.deadline == Some(0) {
+        bail!("--deadline deve ser maior que zero");
+    }
+    if args.watch == Some(0) {
+        bail!("--watch deve ser maior que zero");
+    }
+    if args.suffix_all || !args.suffix_category.is_empty() {
+        let categories: Vec<SuffixCategory> =
+            args.suffix_category.iter().map(|&c| c.into()).collect();
+        args.suffix = known_suffixes(&categories);
+    }
+    if let Some(ref path) = args.suffix_file {
+        args.suffix = parse_suffix_list(&read_input(path)?)
+            .with_context(|| format!("Falha ao ler sufixos de {}", path))?;
+        if args.suffix.is_empty() {
+            bail!("Nenhum sufixo em {}", path);
+        }
+    }
+    // `--suffix .com.br --suffix com.br` chegam iguais depois de normalizados
+    let mut seen_suffixes = HashSet::new();
+    args.suffix
+        .retain(|suffix| seen_suffixes.insert(suffix.clone()));
+
+    // Com saída estruturada no stdout, as mensagens para o usuário vão para o stderr.
+    // Com --quiet e --count-only, elas são descartadas.
+    let mut console: Box<dyn Write> = if args.quiet || args.count_only {
+        Box::new(io::sink())
+    } else if (!matches!(args.format, OutputFormat::Text | OutputFormat::Table)
+        && args.output.is_none())
+        || args.combinations_file.as_deref() == Some("-")
+    {
+        Box::new(io::stderr())
+    } else {
+        Box::new(io::stdout())
+    };
+
+    // Com --workers auto, o stream aceita até --max-workers consultas e o controlador
+    // adaptativo decide quantas ficam de fato em andamento
+    let (workers, workers_label) = match args.workers {
+        Workers::Fixed(workers) => (workers, workers.to_string()),
+        Workers::Auto => (args.max_workers, format!("auto (até {})", args.max_workers)),
+    };
+
+    writeln!(console, "Verificador de Domínios .BR")?;
+    writeln!(console, "==============================")?;
+    writeln!(
+        console,
+        "Sufixo: {} | Workers: {} | Timeout: {}s\n",
+        describe_suffixes(&args.suffix),
+        workers_label,
+        args.timeout
+    )?;
+
+    // Nomes informados pelo usuário são validados e podem ter acentos (convertidos
+    // para punycode). As combinações são geradas sob demanda, conforme os workers
+    // pedem novos alvos.
+    let entries: Option<Vec<String>> = match (&args.check, &args.input, &args.wordlist) {
+        (Some(check), _, _) => Some(check.split(',').map(str::to_string).collect()),
+        (None, Some(input), _) => Some(parse_domain_list(&read_input(input)?, &args.suffix)),
+        (None, None, Some(wordlist)) => {
+            let words = parse_domain_list(&read_input(wordlist)?, &args.suffix);
+            if args.mutate {
+                Some(mutate_with_digits(words.iter().map(String::as_str)))
+            } else {
+                Some(words)
+            }
+        }
+        (None, None, None) => None,
+    };
+    // Domínios do --exclude-file, normalizados como os do --check
+    let excluded = match args.exclude_file {
+        Some(ref path) => {
+            let content = read_input(path)?;
+            let lines = content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.starts_with('#'));
+            let (excluded, invalid) = normalize_exclusions(lines, &args.suffix);
+            for e in &invalid {
+                writeln!(console, "AVISO: {} em {} (ignorado)", e, path)?;
+            }
+            excluded
+        }
+        None => HashSet::new(),
+    };
+    let is_excluded = {
+        let excluded = excluded.clone();
+        move |name: &str, suffix: &str| excluded.contains(&format!("{}{}", name, suffix))
+    };
+
+    let from_entries = entries.is_some();
+    type Targets = Box<dyn Iterator<Item = (String, String)> + Send>;
+    let (mut targets, mut total, excluded_count): (Targets, u64, u64) = if let Some(entries) =
+        entries
+    {
+        let (mut targets, invalid) =
+            normalize_targets(entries.iter().map(String::as_str), &args.suffix);
+        for e in &invalid {
+            writeln!(console, "AVISO: {} (ignorado)", e)?;
+        }
+        if targets.is_empty() {
+            bail!("Nenhum domínio válido para verificar");
+        }
+        let before = targets.len();
+        targets.retain(|(name, suffix)| !is_excluded(name, suffix));
+        if targets.is_empty() {
+            bail!("Todos os domínios informados estão no --exclude-file");
+        }
+        let count = targets.len() as u64;
+        let excluded_count = (before - targets.len()) as u64;
+        (Box::new(targets.into_iter()), count, excluded_count)
+    } else {
+        let mut chars = build_charset(args.charset.as_deref(), args.letters, args.numbers)?;
+        if args.allow_hyphens && !chars.contains(&'-') {
+            chars.push('-');
+        }
+        let lengths = args.length_range()?;
+        let mut combinations = if args.pronounceable {
+            generate_pronounceable(lengths.clone())
+        } else if args.no_leading_zeros {
+            generate_combinations(lengths.clone(), &chars).without_leading_zeros()
+        } else {
+            generate_combinations(lengths.clone(), &chars)
+        };
+        // --first-char e --last-char valem para a combinação, não para o texto fixo
+        // do --pattern
+        if let Some(ref set) = args.first_char {
+            combinations = combinations.with_first_chars(&set.chars().collect::<Vec<_>>());
+        }
+        if let Some(ref set) = args.last_char {
+            combinations = combinations.with_last_chars(&set.chars().collect::<Vec<_>>());
+        }
+        if args.frequency_order {
+            combinations =
+                combinations.with_char_order(&PORTUGUESE_FREQUENCY.chars().collect::<Vec<_>>());
+        } else if let Some(ref order) = args.char_order {
+            combinations = combinations.with_char_order(&order.chars().collect::<Vec<_>>());
+        }
+        let count = combinations.total();
+        if count == 0 && (args.first_char.is_some() || args.last_char.is_some()) {
+            bail!("Nenhuma combinação atende a --first-char/--last-char com esse conjunto de caracteres");
+        }
+
+        let pattern = match (&args.pattern, &args.prefix) {
+            (Some(pattern), _) => Some(Pattern::parse(pattern)?),
+            (None, Some(prefix)) => Some(Pattern::parse(&format!("{}{{}}", prefix))?),
+            (None, None) => None,
+        };
+        if let Some(ref pattern) = pattern {
+            pattern.check_lengths(&lengths)?;
+        }
+        // --match e --exclude valem para o nome completo, já com o texto do --pattern
+        let filter = LabelFilter::new(args.r#match.as_deref(), args.exclude.as_deref())?;
+        let filtering = filter.is_active();
+        let names = move |combinations: Combinations| {
+            let pattern = pattern.clone();
+            let filter = filter.clone();
+            combinations
+                .map(move |combination| match pattern {
+                    Some(ref pattern) => pattern.apply(&combination),
+                    None => combination,
+                })
+                .filter(move |name| filter.allows(name))
+        };
 
-    /// Apenas letras (sem números)
-    #[arg(long)]
-    letters: bool,
+        // Com filtros ou exclusões, o total só é conhecido percorrendo as combinações
+        // uma vez
+        let suffixes = args.suffix.clone();
+        let count = if filtering {
+            names(combinations.clone()).count() as u64
+        } else {
+            count
+        };
+        let excluded_count = if excluded.is_empty() {
+            0
+        } else {
+            names(combinations.clone())
+                .map(|name| {
+                    suffixes
+                        .iter()
+                        .filter(|suffix| is_excluded(&name, suffix))
+                        .count() as u64
+                })
+                .sum()
+        };
 
-    /// Apenas números (sem letras)
-    #[arg(long)]
-    numbers: bool,
+        // Cada nome gerado é verificado em todos os sufixos informados
+        let total = count.saturating_mul(suffixes.len() as u64) - excluded_count;
+        let targets = names(combinations)
+            .flat_map(move |name| {
+                suffixes
+                    .clone()
+                    .into_iter()
+                    .map(move |suffix| (name.clone(), suffix))
+            })
+            .filter(move |(name, suffix)| !is_excluded(name, suffix));
+        (Box::new(targets), total, excluded_count)
+    };
+    if args.exclude_file.is_some() {
+        writeln!(
+            console,
+            "Excluídos pelo --exclude-file: {} domínios",
+            excluded_count
+        )?;
+    }
 
-    /// Arquivo para salvar domínios disponíveis
-    #[arg(short, long)]
-    output: Option<String>,
+    // O total das combinações é calculado sem gerá-las, a não ser que um filtro
+    // precise ver cada nome
+    if args.count_only {
+        println!("{}", total);
+        return Ok(ExitCode::SUCCESS);
+    }
 
-    /// Verificar domínio(s) específico(s), separados por vírgula
-    #[arg(short, long)]
-    check: Option<String>,
+    // A amostra é sorteada logo após a geração, antes do --resume e do --limit; como
+    // ela é tirada durante o stream, o total passa a ser uma estimativa
+    if let Some(rate) = args.sample_rate {
+        targets = Box::new(sample(targets, rate, args.seed));
+        total = (total as f64 * rate).round() as u64;
+    }
 
-    /// Mostra todos os domínios verificados
-    #[arg(short, long)]
-    verbose: bool,
-}
+    // No modo --resume, pula o que já foi verificado e preserva os resultados anteriores
+    let mut previous_results = Vec::new();
+    let mut progress_file = None;
+    if args.resume {
+        let output_file = args.output.as_deref().unwrap_or_default();
+        let progress_file_path = progress_path(output_file);
+        previous_results = read_previous_results(output_file, args.format)?;
 
-/// Resposta da API de disponibilidade do Registro.br
-/// status: 0 = disponível, 2 = registrado, 3 = em processo, 4 = indisponível
-#[derive(Debug, Deserialize)]
-struct AvailResponse {
-    status: i32,
-    fqdn: String,
-    #[serde(rename = "publication-status")]
-    publication_status: Option<String>,
-    #[serde(rename = "expires-at")]
-    expires_at: Option<String>,
-}
+        let mut checked = read_progress(&progress_file_path)?;
+        checked.extend(previous_results.iter().map(|r| r.domain.clone()));
 
-#[derive(Debug, Clone)]
-struct DomainResult {
-    domain: String,
-    available: bool,
-    status: Option<String>,
-    error: Option<String>,
-}
+        writeln!(
+            console,
+            "Retomando: {} domínios já verificados serão ignorados",
+            checked.len()
+        )?;
+        total = total.saturating_sub(checked.len() as u64);
+        targets =
+            Box::new(targets.filter(move |(domain, suffix)| {
+                !checked.contains(&format!("{}{}", domain, suffix))
+            }));
 
-fn generate_combinations(length: u8, letters_only: bool, numbers_only: bool) -> Vec<String> {
-    let chars: Vec<char> = if numbers_only {
-        "0123456789".chars().collect()
-    } else if letters_only {
-        "abcdefghijklmnopqrstuvwxyz".chars().collect()
-    } else {
-        "abcdefghijklmnopqrstuvwxyz0123456789".chars().collect()
-    };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&progress_file_path)
+            .with_context(|| format!("Falha ao abrir arquivo {}", progress_file_path))?;
+        progress_file = Some(Arc::new(std::sync::Mutex::new(file)));
+    }
 
-    let mut combinations = Vec::new();
-    let base = chars.len();
-    let total = base.pow(length as u32);
+    // Embaralhar exige conhecer todos os alvos, então aqui a lista é materializada
+    if args.shuffle {
+        let mut shuffled: Vec<_> = targets.collect();
+        shuffle(&mut shuffled, args.seed);
+        total = shuffled.len() as u64;
+        targets = Box::new(shuffled.into_iter());
+    }
 
-    for i in 0..total {
-        let mut combo = String::with_capacity(length as usize);
-        let mut n = i;
-        for _ in 0..length {
-            combo.push(chars[n % base]);
-            n /= base;
-        }
-        combinations.push(combo.chars().rev().collect());
+    if let Some(limit) = args.limit {
+        targets = Box::new(targets.take(limit));
+        total = total.min(limit as u64);
     }
 
-    combinations
-}
+    let runtime = estimate_runtime(
+        total,
+        workers,
+        ESTIMATED_LATENCY,
+        Duration::from_millis(args.delay),
+        args.max_rps,
+    );
+    match args.sample_rate {
+        Some(rate) => writeln!(
+            console,
+            "Total de domínios a verificar: ~{} (amostra de {}%)",
+            total,
+            rate * 100.0
+        )?,
+        None => writeln!(console, "Total de domínios a verificar: {}", total)?,
+    }
+    writeln!(console, "Duração estimada: ~{}\n", format_runtime(runtime))?;
 
-async fn check_domain(client: &Client, domain: &str, suffix: &str) -> DomainResult {
-    let full_domain = format!("{}{}", domain, suffix);
-    let url = format!("{}{}", AVAIL_API_URL, full_domain);
+    // --combinations-file: a mesma lista do --dry-run -v, mas num arquivo, para usar a
+    // geração como gerador de wordlists
+    if let Some(ref path) = args.combinations_file {
+        let mut writer = open_output(Some(path.as_str()).filter(|&path| path != "-"), false)?;
+        let mut written = 0u64;
+        for (domain, suffix) in targets {
+            writeln!(writer, "{}{}", domain, suffix)?;
+            written += 1;
+        }
+        writer.flush()?;
+        if path != "-" {
+            writeln!(console, "{} domínios gravados em {}", written, path)?;
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
 
-    match client
-        .get(&url)
-        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7)")
-        .send()
-        .await
-    {
-        Ok(response) => {
-            let status_code = response.status();
-
-            if status_code == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                return DomainResult {
-                    domain: full_domain,
-                    available: false,
-                    status: None,
-                    error: Some("rate limited".to_string()),
-                };
+    // --dry-run: apenas mostra o que seria verificado, sem tocar na rede
+    if args.dry_run {
+        if args.verbose {
+            for (domain, suffix) in targets {
+                writeln!(console, "{}{}", domain, suffix)?;
             }
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
 
-            if status_code.is_success() {
-                match response.json::<AvailResponse>().await {
-                    Ok(avail) => {
-                        // status: 0 = disponível, 2 = registrado, 3 = em processo, 4 = indisponível
-                        let available = avail.status == 0;
-                        let status_str = match avail.status {
-                            0 => "disponível".to_string(),
-                            2 => {
-                                if let Some(expires) = avail.expires_at {
-                                    format!("registrado (expira: {})", expires.split('T').next().unwrap_or(&expires))
-                                } else {
-                                    "registrado".to_string()
-                                }
-                            }
-                            3 => "em processo".to_string(),
-                            4 => "indisponível".to_string(),
-                            _ => format!("status {}", avail.status),
-                        };
-                        DomainResult {
-                            domain: full_domain,
-                            available,
-                            status: Some(status_str),
-                            error: None,
-                        }
-                    }
-                    Err(e) => DomainResult {
-                        domain: full_domain,
-                        available: false,
-                        status: None,
-                        error: Some(format!("parse error: {}", e)),
-                    },
-                }
-            } else {
-                DomainResult {
-                    domain: full_domain,
-                    available: false,
-                    status: None,
-                    error: Some(format!("HTTP {}", status_code)),
-                }
+    // Varreduras grandes demoram horas e chamam a atenção do rate limiting do
+    // Registro.br, então precisam ser confirmadas: no terminal, com uma pergunta; fora
+    // dele (cron, CI), só com --yes
+    let over_count = total > args.confirm_above;
+    let over_runtime = runtime > Duration::from_secs(args.confirm_runtime);
+    if (over_count || over_runtime) && !args.yes {
+        let reason = if over_count {
+            format!(
+                "{} domínios excedem o limite de {} (--confirm-above)",
+                total, args.confirm_above
+            )
+        } else {
+            format!(
+                "A duração estimada (~{}) excede {} (--confirm-runtime)",
+                format_runtime(runtime),
+                format_runtime(Duration::from_secs(args.confirm_runtime))
+            )
+        };
+        match ask_confirmation(&format!("{}. Continuar?", reason))? {
+            Some(true) => {}
+            Some(false) => {
+                writeln!(console, "Varredura cancelada")?;
+                return Ok(ExitCode::SUCCESS);
             }
+            None => bail!(
+                "{}. Use --yes para confirmar, ou reduza a busca com --limit, --pattern ou \
+                 um tamanho menor",
+                reason
+            ),
         }
-        Err(e) => DomainResult {
-            domain: full_domain,
-            available: false,
-            status: None,
-            error: Some(e.to_string()),
-        },
     }
-}
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
 
-    println!("Verificador de Domínios .BR");
-    println!("==============================");
-    println!(
-        "Sufixo: {} | Workers: {} | Timeout: {}s\n",
-        args.suffix, args.workers, args.timeout
-    );
+    // Sem --proxy, o reqwest usa HTTPS_PROXY/HTTP_PROXY/ALL_PROXY do ambiente
+    // Todas as consultas vão para o mesmo host: manter uma conexão ociosa por worker
+    // evita reabrir conexões TLS a cada requisição
+    let connections = ConnectionCounter::new();
+    let mut client = Client::builder()
+        .timeout(Duration::from_secs(args.timeout))
+        .pool_max_idle_per_host(args.pool_max_idle.unwrap_or(workers))
+        .pool_idle_timeout(Duration::from_secs(args.pool_idle_timeout))
+        .connector_layer(connections.clone());
+    if let Some(secs) = args.connect_timeout {
+        client = client.connect_timeout(Duration::from_secs(secs));
+    }
+    // Um redirecionamento da API de disponibilidade indica login ou bloqueio: segui-lo
+    // trocaria o resultado por um erro de interpretação da página de destino
+    if !args.follow_redirects {
+        client = client.redirect(reqwest::redirect::Policy::none());
+    }
+    // Os cookies ficam só na memória do cliente, compartilhados por todos os workers
+    if args.cookies {
+        client = client.cookie_store(true);
+    }
+    // No modo auto, HTTPS usa HTTP/2 quando o servidor o oferece via ALPN. Com 2, todas
+    // as requisições são multiplexadas sem negociação, o que falha contra um servidor
+    // só HTTP/1.1.
+    client = match args.http_version {
+        HttpVersion::Auto => client,
+        HttpVersion::Http1 => client.http1_only(),
+        HttpVersion::Http2 => client.http2_prior_knowledge(),
+    };
+    if let Some(ref proxy) = args.proxy {
+        client =
+            client.proxy(Proxy::all(proxy).with_context(|| format!("Proxy inválido: {}", proxy))?);
+    }
+    let client = client.build().context("Falha ao criar cliente HTTP")?;
 
-    let domains: Vec<String> = if let Some(ref check) = args.check {
-        check.split(',').map(|s| s.trim().to_string()).collect()
+    // Com --no-progress a barra continua contando, só não é desenhada
+    let draw_target = if args.quiet || args.no_progress {
+        ProgressDrawTarget::hidden()
     } else {
-        generate_combinations(args.digits, args.letters, args.numbers)
+        ProgressDrawTarget::stderr()
     };
-
-    println!("Total de domínios a verificar: {}\n", domains.len());
-
-    let client = Client::builder()
-        .timeout(Duration::from_secs(args.timeout))
-        .build()
-        .context("Falha ao criar cliente HTTP")?;
-
-    let progress = ProgressBar::new(domains.len() as u64);
+    let progress = ProgressBar::with_draw_target(Some(total), draw_target);
     progress.set_style(
         ProgressStyle::default_bar()
             .template(
-                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) | {msg}",
+                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({recent_eta}) | {msg}",
             )?
+            .with_key("recent_eta", RecentEta::new())
             .progress_chars("##-"),
     );
 
+    // As linhas de resultado e os logs vão para o stderr; arquivos de saída nunca têm cor
+    let color = args.color.enabled(
+        io::stderr().is_terminal(),
+        std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()),
+    );
+    if args.log_level != LogLevel::Off {
+        tracing_subscriber::fmt()
+            .with_max_level(LevelFilter::from(args.log_level))
+            .with_ansi(color)
+            .with_writer(ProgressWriter(progress.clone()))
+            .init();
+    }
+
     let available_count = Arc::new(AtomicUsize::new(0));
     let error_count = Arc::new(AtomicUsize::new(0));
     let available_domains = Arc::new(tokio::sync::Mutex::new(Vec::new()));
 
-    let suffix = args.suffix.clone();
+    let retry = RetryPolicy {
+        retries: args.retries,
+        base_delay: Duration::from_millis(args.retry_delay),
+    };
+    let mut history = args.sqlite.as_deref().map(History::open).transpose()?;
+    // Lido antes da varredura para que um arquivo inválido não desperdice a execução
+    let compare_with = match args.compare {
+        Some(ref path) => {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Falha ao ler arquivo {}", path))?;
+            let previous = parse_json_results(&content)
+                .with_context(|| format!("Falha ao interpretar {} como JSON", path))?;
+            Some((path, previous))
+        }
+        None => None,
+    };
+    let cache = match args.cache {
+        Some(ref path) => {
+            Some(ResultCache::load(path, Duration::from_secs(args.cache_ttl))?.shared())
+        }
+        None => None,
+    };
+
+    // As notificações usam o mesmo cliente (e, portanto, o mesmo proxy e timeout)
+    let webhook = args.webhook.as_ref().map(|url| {
+        let progress = progress.clone();
+        WebhookNotifier::spawn(
+            client.clone(),
+            url.clone(),
+            DEFAULT_BATCH_WINDOW,
+            move |e| print_above(&progress, format!("   AVISO: {}", e)),
+        )
+    });
+    let notifier = webhook.as_ref();
+
+    let mut checker = Checker::new(client)
+        .with_retry(retry)
+        .with_rate_limiter(RateLimiter::shared(args.max_rps))
+        .with_delay(Duration::from_millis(args.delay));
+    if let Some(ref user_agent) = args.user_agent {
+        checker = checker.with_user_agent(user_agent.as_str());
+    }
+    if args.rotate_user_agents {
+        checker = checker.with_rotating_user_agents(USER_AGENT_POOL.iter().copied());
+    }
+    if let Some(ref cache) = cache {
+        checker = checker.with_cache(cache.clone());
+    }
+    match (args.backend, args.api_url.as_deref()) {
+        (BackendKind::Avail, None) => {}
+        (BackendKind::Rdap, None) => checker = checker.with_backend(RdapBackend),
+        (BackendKind::Avail, Some(url)) => {
+            checker = checker.with_backend(CustomUrl::new(url, AvailBackend)?)
+        }
+        (BackendKind::Rdap, Some(url)) => {
+            checker = checker.with_backend(CustomUrl::new(url, RdapBackend)?)
+        }
+    }
+    if args.raw {
+        checker = checker.with_raw_bodies(true);
+    }
+    if args.retry_on_parse_error {
+        checker = checker.with_parse_error_retries(true);
+    }
+    if args.dns_prefilter {
+        checker = checker.with_dns_prefilter(DnsPrefilter::from_system_conf()?);
+    }
+    let concurrency = (args.workers == Workers::Auto)
+        .then(|| AdaptiveConcurrency::shared(INITIAL_AUTO_WORKERS, args.max_workers));
+    if let Some(ref concurrency) = concurrency {
+        checker = checker.with_adaptive_concurrency(concurrency.clone());
+    }
+    let breaker = args
+        .max_error_rate
+        .map(|rate| CircuitBreaker::shared(rate, BREAKER_WINDOW, BREAKER_MIN_SAMPLES));
+    if let Some(ref breaker) = breaker {
+        checker = checker.with_circuit_breaker(breaker.clone());
+    }
+    let hedging = args
+        .hedge_after
+        .map(|ms| HedgeBudget::shared(Duration::from_millis(ms), HEDGE_RATIO, HEDGE_BURST));
+    if let Some(ref hedging) = hedging {
+        checker = checker.with_hedging(hedging.clone());
+    }
+    // O servidor vive enquanto o programa roda, inclusive entre as rodadas do --watch
+    if let Some(port) = args.metrics_port {
+        let addr = SocketAddr::new(args.metrics_addr, port);
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Falha ao abrir {} para as métricas", addr))?;
+        let metrics = Metrics::shared();
+        checker = checker.with_metrics(metrics.clone());
+        metrics::serve(listener, metrics);
+        writeln!(console, "Métricas em http://{}/metrics", addr)?;
+    }
+    // Uma consulta a um domínio registrado antes de começar: sem ela, uma falha de rede
+    // ou uma mudança na API só aparece depois de a varredura inteira dar erro
+    if !args.skip_probe {
+        let probe = checker.probe().await;
+        if let Some(error) = probe.error {
+            bail!(
+                "A consulta de teste a {} falhou ({}). Verifique a conexão, o --proxy e o \
+                 --api-url, ou use --skip-probe para pular o teste",
+                PROBE_DOMAIN,
+                error
+            );
+        }
+        if probe.available {
+            bail!(
+                "A API informou {} como disponível, mas ele é registrado: o formato da resposta \
+                 pode ter mudado. Use --skip-probe para verificar mesmo assim",
+                PROBE_DOMAIN
+            );
+        }
+    }
     let verbose = args.verbose;
+    let raw = args.raw;
+    let quiet = args.quiet;
+    let only: Vec<StatusKind> = args.only.iter().map(|&filter| filter.into()).collect();
+    let only = &only;
+    let save_all = args.save_all;
+
+    // Primeiro Ctrl+C: para de enviar novos domínios e aguarda os que estão em andamento.
+    // Segundo Ctrl+C: encerra imediatamente.
+    let interrupted = CancellationToken::new();
+    {
+        let interrupted = interrupted.clone();
+        let progress = progress.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                interrupted.cancel();
+                print_above(
+                    &progress,
+                    "Interrompendo: aguardando requisições em andamento (Ctrl+C de novo para sair)",
+                );
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    std::process::exit(130);
+                }
+            }
+        });
+    }
+
+    if let Some(secs) = args.watch {
+        progress.set_draw_target(ProgressDrawTarget::hidden());
+        let targets: Vec<_> = targets.collect();
+        watch(
+            &checker,
+            targets,
+            workers,
+            Duration::from_secs(secs),
+            notifier,
+            &interrupted,
+            &mut *console,
+        )
+        .await?;
+        if let Some(webhook) = webhook {
+            webhook.finish().await;
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    // O painel substitui a barra de progresso, mas só faz sentido em um terminal
+    let dashboard = if args.tui && io::stdout().is_terminal() {
+        progress.set_draw_target(ProgressDrawTarget::hidden());
+        Some(Dashboard::start(total, interrupted.clone()).context("Falha ao iniciar o painel")?)
+    } else {
+        if args.tui {
+            writeln!(
+                console,
+                "--tui requer um terminal; usando a barra de progresso"
+            )?;
+        }
+        None
+    };
+    let dashboard_state = dashboard.as_ref().map(Dashboard::state);
+    // O painel é dono do terminal: nada é impresso por cima dele
+    let print_results = !quiet && dashboard.is_none();
+    // Poucos domínios informados à mão (--check loja): só uma linha por domínio, com o
+    // status e a expiração, no lugar da barra e do RESUMO
+    let concise = print_results && from_entries && total <= CONCISE_MAX_DOMAINS && !args.stats;
+    if concise {
+        progress.set_draw_target(ProgressDrawTarget::hidden());
+    }
+
+    // Fora de um terminal a barra não é desenhada; no lugar dela, uma linha de status
+    // é impressa a cada --progress-interval segundos
+    let status_lines =
+        (!io::stderr().is_terminal() && print_results && !concise && args.progress_interval > 0)
+            .then(|| {
+                let progress = progress.clone();
+                let available_count = available_count.clone();
+                let error_count = error_count.clone();
+                let period = Duration::from_secs(args.progress_interval);
+                tokio::spawn(async move {
+                    let mut ticks =
+                        tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                    loop {
+                        ticks.tick().await;
+                        eprintln!(
+                            "verificados {}/{}, {} disponíveis, {} erros",
+                            progress.position(),
+                            total,
+                            available_count.load(Ordering::Relaxed),
+                            error_count.load(Ordering::Relaxed)
+                        );
+                    }
+                })
+            });
+
+    // Com --min-available, a meta atingida tem o mesmo efeito do primeiro Ctrl+C: as
+    // requisições em andamento terminam, então o total pode passar um pouco de N
+    let min_available = args.min_available;
+    let goal_reached = {
+        let available_count = available_count.clone();
+        move || min_available.is_some_and(|n| available_count.load(Ordering::Relaxed) >= n)
+    };
+    // Com --max-error-rate, o disjuntor aberto também para o envio de domínios
+    let tripped = {
+        let breaker = breaker.clone();
+        move || breaker.as_ref().is_some_and(|breaker| breaker.is_open())
+    };
+    // Com --deadline, o prazo esgotado também: o que estava em andamento ainda entra no
+    // resultado parcial. O aviso fica registrado só se sobraram domínios sem enviar.
+    let deadline = args
+        .deadline
+        .map(|secs| Instant::now() + Duration::from_secs(secs));
+    let expired = Arc::new(AtomicBool::new(false));
+    let feed = {
+        let goal_reached = goal_reached.clone();
+        let tripped = tripped.clone();
+        let expired = expired.clone();
+        targets.take_while(move |_| {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                expired.store(true, Ordering::Relaxed);
+                return false;
+            }
+            !goal_reached() && !tripped()
+        })
+    };
+
+    // Com --append, o que já está no arquivo de saída não é gravado de novo
+    let already_saved: Arc<HashSet<String>> = Arc::new(match args.output {
+        Some(ref path) if args.append => read_previous_results(path, args.format)?
+            .into_iter()
+            .map(|r| r.domain)
+            .collect(),
+        _ => HashSet::new(),
+    });
+
+    // NDJSON é escrito durante a varredura; ao retomar ou com --append, o arquivo
+    // anterior é estendido
+    let ndjson: Option<Arc<std::sync::Mutex<Box<dyn Write + Send>>>> =
+        if args.format == OutputFormat::Ndjson {
+            let writer: Box<dyn Write + Send> = match args.output {
+                Some(ref path) => Box::new(create_output_file(path, args.resume || args.append)?),
+                None => Box::new(io::stdout()),
+            };
+            Some(Arc::new(std::sync::Mutex::new(writer)))
+        } else {
+            None
+        };
 
-    let results: Vec<DomainResult> = stream::iter(domains)
-        .map(|domain| {
-            let client = client.clone();
-            let suffix = suffix.clone();
+    // Cada código de status não documentado gera um aviso na primeira vez que aparece
+    let unknown_codes: Arc<std::sync::Mutex<HashSet<i32>>> = Arc::default();
+    let started = Instant::now();
+    let started_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let results: Vec<DomainResult> = checker
+        .check_stream_cancellable(feed, workers, interrupted.clone(), OnCancel::Finish)
+        .then(|result| {
+            let checker = checker.clone();
             let progress = progress.clone();
             let available_count = available_count.clone();
             let error_count = error_count.clone();
             let available_domains = available_domains.clone();
+            let progress_file = progress_file.clone();
+            let dashboard_state = dashboard_state.clone();
+            let ndjson = ndjson.clone();
+            let already_saved = already_saved.clone();
+            let unknown_codes = unknown_codes.clone();
 
             async move {
-                let result = check_domain(&client, &domain, &suffix).await;
+                if let Some(ref state) = dashboard_state {
+                    state.lock().unwrap().record(&result);
+                }
+
+                if let (true, StatusKind::Unknown, Some(code)) =
+                    (print_results, result.kind(), result.status_code)
+                {
+                    if unknown_codes.lock().unwrap().insert(code) {
+                        print_above(
+                            &progress,
+                            format!(
+                                "   AVISO: {} respondeu com o código de status {}, que a API não \
+                                 documenta (contado como status desconhecido)",
+                                result.domain, code
+                            ),
+                        );
+                    }
+                }
+
+                // Domínios com erro ficam de fora para serem tentados de novo ao retomar
+                if let (Some(file), None) = (&progress_file, &result.error) {
+                    let mut file = file.lock().unwrap();
+                    if let Err(e) = writeln!(file, "{}", result.domain) {
+                        print_above(
+                            &progress,
+                            format!("   AVISO: falha ao gravar progresso ({})", e),
+                        );
+                    }
+                }
+
+                // Com --only, apenas os status escolhidos são exibidos e gravados; com
+                // --quiet, apenas os disponíveis
+                let kind = result.kind();
+                if let Some(ref writer) = ndjson {
+                    if (save_all || select(only, quiet, &result))
+                        && !already_saved.contains(&result.domain)
+                    {
+                        let mut writer = writer.lock().unwrap();
+                        if let Err(e) = write_ndjson_line(&mut *writer, &result) {
+                            print_above(
+                                &progress,
+                                format!("   AVISO: falha ao gravar resultado ({})", e),
+                            );
+                        }
+                    }
+                }
+
+                // Domínios em liberação são os mais disputados: aparecem mesmo sem -v
+                let shown = print_results
+                    && if only.is_empty() {
+                        matches!(kind, StatusKind::Available | StatusKind::Release)
+                            || verbose
+                            || concise
+                    } else {
+                        only.contains(&kind)
+                    };
 
                 if result.available {
                     available_count.fetch_add(1, Ordering::Relaxed);
+                    if let Some(notifier) = notifier {
+                        notifier.notify(&result);
+                    }
                     let mut domains = available_domains.lock().await;
                     domains.push(result.domain.clone());
-                    progress.println(format!("DISPONIVEL: {}", result.domain));
+                    if shown {
+                        print_above(
+                            &progress,
+                            paint(
+                                &format!("DISPONIVEL: {}", result.display_name_with_restriction()),
+                                AnsiColors::Green,
+                                color,
+                            ),
+                        );
+                    }
                 } else if result.error.is_some() {
                     error_count.fetch_add(1, Ordering::Relaxed);
-                    if verbose {
-                        progress.println(format!(
-                            "   ERRO: {} ({})",
-                            result.domain,
-                            result.error.as_ref().unwrap()
-                        ));
+                    if shown {
+                        print_above(
+                            &progress,
+                            paint(
+                                &format!(
+                                    "   ERRO: {} ({})",
+                                    result.display_name(),
+                                    result.error.as_ref().unwrap()
+                                ),
+                                AnsiColors::Red,
+                                color,
+                            ),
+                        );
                     }
-                } else if verbose {
-                    progress.println(format!(
-                        "   REGISTRADO: {} ({})",
-                        result.domain,
-                        result.status.as_ref().unwrap_or(&"registrado".to_string())
-                    ));
+                } else if kind == StatusKind::Release && shown {
+                    print_above(
+                        &progress,
+                        paint(
+                            &format!(
+                                "EM LIBERACAO: {} ({})",
+                                result.display_name(),
+                                result.status.as_deref().unwrap_or(RELEASE_STATUS)
+                            ),
+                            AnsiColors::Cyan,
+                            color,
+                        ),
+                    );
+                } else if kind == StatusKind::Unknown && shown {
+                    print_above(
+                        &progress,
+                        paint(
+                            &format!(
+                                "   DESCONHECIDO: {} ({})",
+                                result.display_name(),
+                                result.status.as_deref().unwrap_or(UNKNOWN_STATUS)
+                            ),
+                            AnsiColors::Magenta,
+                            color,
+                        ),
+                    );
+                } else if shown {
+                    print_above(
+                        &progress,
+                        paint(
+                            &format!(
+                                "   REGISTRADO: {} ({})",
+                                result.display_name(),
+                                result.status.as_ref().unwrap_or(&"registrado".to_string())
+                            ),
+                            AnsiColors::Yellow,
+                            color,
+                        ),
+                    );
+                }
+
+                // Resultados do cache e do DNS não têm corpo para mostrar
+                if let Some(body) = result.raw.as_ref().filter(|_| shown && raw) {
+                    print_above(&progress, format!("      resposta: {}", body.trim()));
                 }
 
                 progress.inc(1);
-                progress.set_message(format!(
-                    "{} disponiveis",
-                    available_count.load(Ordering::Relaxed)
-                ));
+                let available = available_count.load(Ordering::Relaxed);
+                let rate = checker.rate_limiter().lock().unwrap().rate();
+                if rate.is_finite() {
+                    progress.set_message(format!("{} disponiveis | {:.1} req/s", available, rate));
+                } else {
+                    progress.set_message(format!("{} disponiveis", available));
+                }
 
                 result
             }
         })
-        .buffer_unordered(args.workers)
         .collect()
         .await;
+    let elapsed = started.elapsed();
+    if let Some(status_lines) = status_lines {
+        status_lines.abort();
+    }
+    if let Some(webhook) = webhook {
+        webhook.finish().await;
+    }
+    if let Some(dashboard) = dashboard {
+        dashboard
+            .finish()
+            .context("Falha ao restaurar o terminal")?;
+    }
 
     progress.finish_with_message(format!(
         "{} disponiveis, {} erros",
@@ -264,94 +2294,747 @@ async fn main() -> Result<()> {
         error_count.load(Ordering::Relaxed)
     ));
 
+    if let (Some(cache), Some(path)) = (&cache, &args.cache) {
+        cache.lock().unwrap().save(path)?;
+    }
+
+    let mut results = results;
+    sort_results(&mut results, args.sort);
+
     // Resumo final
     let available: Vec<_> = results.iter().filter(|r| r.available).collect();
 
-    println!("\n==============================");
-    println!("RESUMO");
-    println!("==============================");
-    println!("Total verificado: {}", results.len());
-    println!("Disponíveis: {}", available.len());
-    println!("Erros: {}", error_count.load(Ordering::Relaxed));
+    if !concise {
+        writeln!(console, "\n==============================")?;
+        writeln!(console, "RESUMO")?;
+        writeln!(console, "==============================")?;
+        writeln!(console, "Total verificado: {}", results.len())?;
+        writeln!(console, "Disponíveis: {}", available.len())?;
+        writeln!(console, "Erros: {}", error_count.load(Ordering::Relaxed))?;
+        let breakdown = StatusBreakdown::from_results(&results);
+        write!(
+            console,
+            "Registrados: {}, Em processo: {}, Em liberação: {}, Indisponíveis: {}, \
+             Rate-limited: {}",
+            breakdown.registered,
+            breakdown.in_process,
+            breakdown.release,
+            breakdown.unavailable,
+            breakdown.rate_limited
+        )?;
+        if breakdown.unknown > 0 {
+            write!(console, ", Status desconhecido: {}", breakdown.unknown)?;
+            if !breakdown.unknown_codes.is_empty() {
+                let codes: Vec<String> = breakdown
+                    .unknown_codes
+                    .iter()
+                    .map(|code| code.to_string())
+                    .collect();
+                let label = if codes.len() == 1 {
+                    "código"
+                } else {
+                    "códigos"
+                };
+                write!(console, " ({} {})", label, codes.join(", "))?;
+            }
+        }
+        writeln!(console)?;
+    }
+    if args.dns_prefilter && !concise {
+        writeln!(
+            console,
+            "Registrados pelo DNS (sem consulta à API): {}",
+            results.iter().filter(|r| r.is_dns_confirmed()).count()
+        )?;
+    }
+    if let Some(breaker) = breaker.as_ref().filter(|breaker| breaker.is_open()) {
+        let (rate, samples) = breaker.error_rate();
+        writeln!(
+            console,
+            "Varredura abortada: {:.0}% de erros nas últimas {} consultas (--max-error-rate {}). \
+             A API pode estar fora do ar ou bloqueando este IP; {} de {} domínios verificados",
+            rate * 100.0,
+            samples,
+            args.max_error_rate.unwrap_or_default(),
+            results.len(),
+            total
+        )?;
+    } else if interrupted.is_cancelled() {
+        writeln!(
+            console,
+            "Varredura interrompida: {} de {} domínios verificados",
+            results.len(),
+            total
+        )?;
+    } else if expired.load(Ordering::Relaxed) {
+        writeln!(
+            console,
+            "Prazo de {}s esgotado: {} de {} domínios verificados",
+            args.deadline.unwrap_or_default(),
+            results.len(),
+            total
+        )?;
+    } else if let Some(n) = min_available.filter(|_| goal_reached()) {
+        writeln!(
+            console,
+            "Meta de {} disponíveis atingida: {} de {} domínios verificados",
+            n,
+            results.len(),
+            total
+        )?;
+    }
+
+    if args.stats {
+        let latency = LatencyStats::from_results(&results);
+        match latency {
+            Some(stats) => writeln!(
+                console,
+                "Latência ({} consultas): mín {} ms | mediana {} ms | p95 {} ms | máx {} ms",
+                stats.count,
+                stats.min.as_millis(),
+                stats.median.as_millis(),
+                stats.p95.as_millis(),
+                stats.max.as_millis()
+            )?,
+            None => writeln!(console, "Latência: nenhuma consulta feita à API")?,
+        }
+        // Bem abaixo do número de consultas indica que o keep-alive está funcionando
+        writeln!(
+            console,
+            "Conexões abertas: {} para {} consultas",
+            connections.opened(),
+            latency.map_or(0, |stats| stats.count)
+        )?;
+        writeln!(
+            console,
+            "Taxa média: {:.1} req/s em {:.1}s",
+            results.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+            elapsed.as_secs_f64()
+        )?;
+        if let Some(ref concurrency) = concurrency {
+            writeln!(console, "Workers ao final: {}", concurrency.limit())?;
+        }
+        if let Some(ref hedging) = hedging {
+            writeln!(console, "Requisições de reserva: {}", hedging.hedged())?;
+        }
+    }
 
-    if !available.is_empty() {
-        println!("\nDOMÍNIOS DISPONÍVEIS:");
+    if !available.is_empty() && !concise {
+        writeln!(console, "\nDOMÍNIOS DISPONÍVEIS:")?;
         for d in &available {
-            println!("   - {}", d.domain);
+            writeln!(console, "   - {}", d.display_name_with_restriction())?;
+        }
+    }
+
+    if let Some(days) = args.expiring_within {
+        let expiring = expiring_within(&results, days);
+        writeln!(
+            console,
+            "\nEXPIRANDO EM ATÉ {} DIAS: {}",
+            days,
+            expiring.len()
+        )?;
+        for d in &expiring {
+            match d.expires_in_days {
+                Some(left) if left < 0 => writeln!(
+                    console,
+                    "   - {} (expirou há {} dias)",
+                    d.display_name(),
+                    -left
+                )?,
+                Some(left) => writeln!(console, "   - {} ({} dias)", d.display_name(), left)?,
+                None => {}
+            }
+        }
+    }
+
+    if let Some((path, ref previous)) = compare_with {
+        let comparison = compare_runs(previous, &results);
+        writeln!(console, "\nMUDANÇAS EM RELAÇÃO A {}:", path)?;
+        let sections = [
+            ("Novos disponíveis", &comparison.newly_available),
+            ("Novos registrados", &comparison.newly_registered),
+            ("Novos erros", &comparison.newly_erroring),
+        ];
+        if sections.iter().all(|(_, domains)| domains.is_empty()) {
+            writeln!(console, "   Nenhuma")?;
+        }
+        for (title, domains) in sections.iter().filter(|(_, d)| !d.is_empty()) {
+            writeln!(console, "   {} ({}):", title, domains.len())?;
+            for domain in domains.iter() {
+                writeln!(console, "      - {}", domain)?;
+            }
         }
     }
 
-    // Salva em arquivo se especificado
-    if let Some(ref output_file) = args.output {
-        if !available.is_empty() {
-            let file = File::create(output_file)
-                .with_context(|| format!("Falha ao criar arquivo {}", output_file))?;
-            let mut writer = BufWriter::new(file);
+    // O histórico recebe só o que foi verificado nesta execução
+    if let (Some(history), Some(path)) = (history.as_mut(), &args.sqlite) {
+        history.record(&started_at, &results)?;
+        writeln!(console, "Histórico gravado em: {}", path)?;
+    }
+
+    // Salva em arquivo se especificado, mantendo o que já havia sido encontrado ao retomar
+    let session_count = results.len();
+    results.splice(0..0, previous_results);
+
+    // Sem --only, o formato text grava só os disponíveis e os demais gravam tudo;
+    // --save-all grava tudo em qualquer formato
+    let mut selected: Vec<DomainResult> = results
+        .iter()
+        .filter(|r| match args.format {
+            _ if args.save_all => true,
+            OutputFormat::Text => (only.is_empty() && r.available) || only.contains(&r.kind()),
+            _ => select(only, quiet, r),
+        })
+        .cloned()
+        .collect();
+    selected.retain(|r| !already_saved.contains(&r.domain));
+    sort_results(&mut selected, args.sort);
+
+    match args.format {
+        OutputFormat::Text => {
+            if let Some(ref output_file) = args.output {
+                let selected: Vec<_> = selected.iter().collect();
+                // Sem o status, a lista completa não distinguiria os disponíveis
+                let with_status = args.with_status || args.save_all;
+                if write_domain_list(output_file, &selected, with_status, args.append)? {
+                    writeln!(console, "\nResultados salvos em: {}", output_file)?;
+                }
+            } else if quiet {
+                let mut stdout = io::stdout().lock();
+                for d in &selected {
+                    writeln!(stdout, "{}", d.domain)?;
+                }
+            }
+        }
+        OutputFormat::Json | OutputFormat::JsonArray | OutputFormat::Csv | OutputFormat::Yaml => {
+            // Ao estender um arquivo que já tem conteúdo, o cabeçalho do CSV não se repete
+            let extending = args.append
+                && args
+                    .output
+                    .as_deref()
+                    .and_then(|path| fs::metadata(path).ok())
+                    .is_some_and(|meta| meta.len() > 0);
+            let writer = open_output(args.output.as_deref(), args.append)?;
+            let report = JsonReport {
+                results: &selected,
+                total: results.len(),
+                available_count: results.iter().filter(|r| r.available).count(),
+                error_count: results.iter().filter(|r| r.error.is_some()).count(),
+                started_at: &started_at,
+                elapsed_secs: elapsed.as_secs_f64(),
+            };
+            match args.format {
+                OutputFormat::Json => write_json_report(writer, &report, args.json_pretty)?,
+                OutputFormat::Yaml => write_yaml_report(writer, &report)?,
+                OutputFormat::JsonArray => write_json(writer, &selected, args.json_pretty)?,
+                _ if extending => append_csv(writer, &selected)?,
+                _ => write_csv(writer, &selected)?,
+            }
 
-            for d in &available {
-                writeln!(writer, "{}", d.domain)?;
+            if let Some(ref output_file) = args.output {
+                writeln!(console, "\nResultados salvos em: {}", output_file)?;
+            }
+        }
+        // A tabela é para leitura no terminal: com --quiet, só ao gravar em arquivo
+        OutputFormat::Table => {
+            if let Some(ref output_file) = args.output {
+                write_table(open_output(Some(output_file), false)?, &selected)?;
+                writeln!(console, "\nResultados salvos em: {}", output_file)?;
+            } else if !quiet {
+                writeln!(console)?;
+                write_table(&mut *console, &selected)?;
+            }
+        }
+        // Já gravado linha a linha durante a varredura
+        OutputFormat::Ndjson => {
+            if let Some(ref output_file) = args.output {
+                writeln!(console, "\nResultados salvos em: {}", output_file)?;
             }
+        }
+    }
+
+    if let Some(ref path) = args.output_available {
+        let available: Vec<_> = results.iter().filter(|r| r.available).collect();
+        if write_domain_list(path, &available, false, false)? {
+            writeln!(console, "Disponíveis salvos em: {}", path)?;
+        }
+    }
+    if let (Some(path), Some(days)) = (&args.output_expiring, args.expiring_within) {
+        if write_domain_list(path, &expiring_within(&results, days), false, false)? {
+            writeln!(
+                console,
+                "Expirando em até {} dias salvos em: {}",
+                days, path
+            )?;
+        }
+    }
 
-            println!("\nResultados salvos em: {}", output_file);
+    // Varredura concluída (sem interrupção, sem atingir o --min-available nem o
+    // --deadline, sem abrir o disjuntor e sem esgotar o --limit): o progresso não é mais
+    // necessário
+    let complete = !interrupted.is_cancelled()
+        && !goal_reached()
+        && !expired.load(Ordering::Relaxed)
+        && !tripped()
+        && args.limit.is_none_or(|limit| session_count < limit);
+    if args.resume && complete {
+        if let Some(ref output_file) = args.output {
+            let _ = fs::remove_file(progress_path(output_file));
         }
     }
 
-    Ok(())
+    let max_errors = if args.fail_on_error {
+        Some(0)
+    } else {
+        args.max_errors
+    };
+    if tripped() {
+        return Ok(ExitCode::from(EXIT_ERRORS));
+    }
+    Ok(ExitCode::from(exit_code(
+        session_count,
+        available_count.load(Ordering::Relaxed),
+        error_count.load(Ordering::Relaxed),
+        max_errors,
+    )))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::{Mutex, MutexGuard, PoisonError};
+
+    /// O ambiente é do processo inteiro: quem define variáveis e quem lê `Args` se
+    /// revezam por este lock
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Variáveis de ambiente definidas enquanto o guard existir, com o [`ENV_LOCK`]
+    struct EnvGuard {
+        vars: Vec<&'static str>,
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl EnvGuard {
+        fn set(vars: &[(&'static str, &str)]) -> Self {
+            let lock = ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+            for &(name, value) in vars {
+                std::env::set_var(name, value);
+            }
+            Self {
+                vars: vars.iter().map(|&(name, _)| name).collect(),
+                _lock: lock,
+            }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            for name in &self.vars {
+                std::env::remove_var(name);
+            }
+        }
+    }
+
+    /// [`parse_args`] sem variáveis de outro teste no ambiente
+    fn parse<I, T>(cli: I) -> Result<Args>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let _env = EnvGuard::set(&[]);
+        parse_args(cli)
+    }
+
+    fn registered(domain: &str, expires: Option<&str>) -> DomainResult {
+        DomainResult {
+            domain: domain.to_string(),
+            available: false,
+            status: Some(match expires {
+                Some(date) => format!("registrado (expira: {})", date),
+                None => "registrado".to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sort_results() {
+        let mut results = vec![
+            registered("zz.com.br", Some("2026-03-01")),
+            registered("abc.com.br", None),
+            registered("ab.com.br", Some("2025-12-01")),
+        ];
+
+        sort_results(&mut results, SortKey::Name);
+        let names: Vec<_> = results.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(names, ["ab.com.br", "abc.com.br", "zz.com.br"]);
+
+        sort_results(&mut results, SortKey::Expiry);
+        let names: Vec<_> = results.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(names, ["ab.com.br", "zz.com.br", "abc.com.br"]);
+
+        sort_results(&mut results, SortKey::Length);
+        let names: Vec<_> = results.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(names, ["ab.com.br", "zz.com.br", "abc.com.br"]);
+    }
+
+    #[test]
+    fn test_shuffle_with_seed_is_reproducible() {
+        let original: Vec<u32> = (0..50).collect();
+
+        let mut first = original.clone();
+        let mut second = original.clone();
+        shuffle(&mut first, Some(42));
+        shuffle(&mut second, Some(42));
+        assert_eq!(first, second);
+        assert_ne!(first, original);
+
+        first.sort();
+        assert_eq!(first, original);
+    }
+
+    #[test]
+    fn test_sample_extremes() {
+        assert_eq!(sample(0..1000, 1.0, None).count(), 1000);
+        assert_eq!(sample(0..1000, 0.0, None).count(), 0);
+
+        let first: Vec<_> = sample(0..1000, 0.1, Some(7)).collect();
+        let second: Vec<_> = sample(0..1000, 0.1, Some(7)).collect();
+        assert_eq!(first, second);
+        assert!((50..150).contains(&first.len()));
+    }
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(exit_code(10, 2, 0, None), EXIT_FOUND);
+        assert_eq!(exit_code(10, 0, 0, None), EXIT_NONE_FOUND);
+        assert_eq!(exit_code(0, 0, 0, None), EXIT_NONE_FOUND);
+        assert_eq!(exit_code(10, 0, 10, None), EXIT_ERRORS);
+        assert_eq!(exit_code(10, 2, 3, None), EXIT_FOUND);
+        assert_eq!(exit_code(10, 2, 3, Some(3)), EXIT_FOUND);
+        assert_eq!(exit_code(10, 2, 4, Some(3)), EXIT_ERRORS);
+        assert_eq!(exit_code(10, 2, 1, Some(0)), EXIT_ERRORS);
+    }
+
+    #[test]
+    fn test_estimate_runtime() {
+        let latency = Duration::from_millis(500);
+
+        // 46.656 domínios com 20 workers: 2.333 rodadas de meio segundo
+        assert_eq!(
+            estimate_runtime(46_656, 20, latency, Duration::ZERO, None),
+            Duration::from_millis(1_166_500)
+        );
+        assert_eq!(
+            estimate_runtime(40, 20, latency, Duration::from_millis(500), None),
+            Duration::from_secs(2)
+        );
+        // A taxa máxima domina quando é mais lenta que os workers
+        assert_eq!(
+            estimate_runtime(100, 20, latency, Duration::ZERO, Some(2.0)),
+            Duration::from_secs(50)
+        );
+        assert_eq!(
+            estimate_runtime(0, 20, latency, Duration::ZERO, None),
+            Duration::ZERO
+        );
+
+        assert_eq!(format_runtime(Duration::from_secs(45)), "45s");
+        assert_eq!(
+            format_runtime(Duration::from_millis(1_166_500)),
+            "19min 26s"
+        );
+        assert_eq!(format_runtime(Duration::from_secs(7_500)), "2h 05min");
+    }
+
+    #[test]
+    fn test_color_mode() {
+        assert!(ColorMode::Auto.enabled(true, false));
+        assert!(!ColorMode::Auto.enabled(true, true));
+        assert!(!ColorMode::Auto.enabled(false, false));
+        assert!(ColorMode::Always.enabled(false, true));
+        assert!(!ColorMode::Never.enabled(true, false));
+
+        assert_eq!(
+            paint("DISPONIVEL: loja.com.br", AnsiColors::Green, true),
+            "\x1b[32mDISPONIVEL: loja.com.br\x1b[39m"
+        );
+        assert_eq!(
+            paint("DISPONIVEL: loja.com.br", AnsiColors::Green, false),
+            "DISPONIVEL: loja.com.br"
+        );
+    }
+
+    #[test]
+    fn test_expiring_within() {
+        let result = |domain: &str, expires_in_days: Option<i64>| DomainResult {
+            domain: domain.to_string(),
+            expires_in_days,
+            ..Default::default()
+        };
+        let results = [
+            result("longe.com.br", Some(200)),
+            result("perto.com.br", Some(10)),
+            result("expirado.com.br", Some(-3)),
+            result("sem-data.com.br", None),
+            result("limite.com.br", Some(30)),
+        ];
+
+        let domains: Vec<_> = expiring_within(&results, 30)
+            .into_iter()
+            .map(|r| r.domain.as_str())
+            .collect();
+        assert_eq!(
+            domains,
+            ["expirado.com.br", "perto.com.br", "limite.com.br"]
+        );
+    }
+
+    #[test]
+    fn test_status_changes() {
+        let result = |domain: &str, status: &str| DomainResult {
+            domain: domain.to_string(),
+            status: Some(status.to_string()),
+            ..Default::default()
+        };
+        let mut previous = HashMap::new();
+        previous.insert("loja.com.br".to_string(), "registrado".to_string());
+        previous.insert("teste.com.br".to_string(), "registrado".to_string());
+
+        let changes = status_changes(
+            &previous,
+            &[
+                result("loja.com.br", "disponível"),
+                result("teste.com.br", "registrado"),
+                result("novo.com.br", "registrado"),
+                DomainResult::failed("erro.com.br".to_string(), "HTTP 500"),
+            ],
+        );
+
+        assert_eq!(
+            changes,
+            [
+                StatusChange {
+                    domain: "loja.com.br".to_string(),
+                    previous: Some("registrado".to_string()),
+                    current: "disponível".to_string(),
+                },
+                StatusChange {
+                    domain: "novo.com.br".to_string(),
+                    previous: None,
+                    current: "registrado".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_runs() {
+        let result = |domain: &str, status: &str, available: bool| DomainResult {
+            domain: domain.to_string(),
+            available,
+            status: Some(status.to_string()),
+            ..Default::default()
+        };
+        let previous = [
+            result("loja.com.br", "registrado (expira: 2026-01-01)", false),
+            result("teste.com.br", "disponível", true),
+            result("renovado.com.br", "registrado (expira: 2026-01-01)", false),
+            result("caiu.com.br", "disponível", true),
+            DomainResult::failed("instavel.com.br".to_string(), "timeout"),
+        ];
+        let current = [
+            result("loja.com.br", "disponível", true),
+            result("teste.com.br", "registrado (expira: 2027-01-01)", false),
+            result("renovado.com.br", "registrado (expira: 2027-01-01)", false),
+            DomainResult::failed("caiu.com.br".to_string(), "HTTP 500"),
+            result("instavel.com.br", "disponível", true),
+            result("novo.com.br", "disponível", true),
+        ];
+
+        assert_eq!(
+            compare_runs(&previous, &current),
+            Comparison {
+                newly_available: vec!["loja.com.br".to_string()],
+                newly_registered: vec!["teste.com.br".to_string()],
+                newly_erroring: vec!["caiu.com.br".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_previous_results_text() {
+        let path = std::env::temp_dir().join("registrobr-finder-test-previous.txt");
+        fs::write(&path, "loja.com.br  disponível\nteste.com.br\n\n").unwrap();
+
+        let results = read_previous_results(path.to_str().unwrap(), OutputFormat::Text).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].domain, "loja.com.br");
+        assert_eq!(results[0].status.as_deref(), Some("disponível"));
+        assert_eq!(results[1].domain, "teste.com.br");
+        assert!(results.iter().all(|r| r.available));
+    }
+
+    #[test]
+    fn test_write_domain_list_skips_empty_subsets() {
+        let path = std::env::temp_dir().join("registrobr-finder-test-list.txt");
+        let path_str = path.to_str().unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert!(!write_domain_list(path_str, &[], false, false).unwrap());
+        assert!(!path.exists());
+
+        let result = DomainResult {
+            domain: "loja.com.br".to_string(),
+            status: Some("disponível".to_string()),
+            ..Default::default()
+        };
+        let failed = DomainResult::failed("teste.com.br".to_string(), "timeout".to_string());
+        assert!(write_domain_list(path_str, &[&result, &failed], true, false).unwrap());
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            content,
+            "loja.com.br  disponível\nteste.com.br  erro (timeout)\n"
+        );
+    }
 
     #[test]
-    fn test_generate_combinations_2_digits_numbers_only() {
-        let combos = generate_combinations(2, false, true);
-        assert_eq!(combos.len(), 100); // 10^2
-        assert!(combos.contains(&"00".to_string()));
-        assert!(combos.contains(&"99".to_string()));
-        assert!(combos.contains(&"42".to_string()));
+    fn test_length_range_respects_label_limits() {
+        let range = |cli: &[&str]| {
+            let mut argv = vec!["registrobr-finder"];
+            argv.extend(cli);
+            parse(argv).unwrap().length_range()
+        };
+        assert_eq!(range(&[]).unwrap(), 2..=2);
+        assert_eq!(
+            range(&["--min-length", "2", "--max-length", "26"]).unwrap(),
+            2..=26
+        );
+        for invalid in [
+            &["-d", "1"][..],
+            &["--min-length", "1", "--max-length", "3"],
+            &["--max-length", "30"],
+        ] {
+            let error = range(invalid).unwrap_err().to_string();
+            assert!(
+                error.contains("fora do permitido"),
+                "{:?}: {}",
+                invalid,
+                error
+            );
+        }
+        // O texto fixo conta no tamanho do nome, conferido por Pattern::check_lengths
+        assert_eq!(range(&["-d", "1", "--prefix", "loja"]).unwrap(), 1..=1);
     }
 
     #[test]
-    fn test_generate_combinations_2_digits_letters_only() {
-        let combos = generate_combinations(2, true, false);
-        assert_eq!(combos.len(), 676); // 26^2
-        assert!(combos.contains(&"aa".to_string()));
-        assert!(combos.contains(&"zz".to_string()));
-        assert!(combos.contains(&"ab".to_string()));
+    fn test_format_inferred_from_output_extension() {
+        let format = |cli: &[&str]| {
+            let mut argv = vec!["registrobr-finder"];
+            argv.extend(cli);
+            parse(argv).unwrap().format
+        };
+        assert_eq!(format(&["-o", "resultados.json"]), OutputFormat::Json);
+        assert_eq!(format(&["-o", "resultados.CSV"]), OutputFormat::Csv);
+        assert_eq!(format(&["-o", "resultados.jsonl"]), OutputFormat::Ndjson);
+        assert_eq!(format(&["-o", "resultados.yml"]), OutputFormat::Yaml);
+        assert_eq!(format(&["-o", "resultados.txt"]), OutputFormat::Text);
+        assert_eq!(format(&["-o", "resultados"]), OutputFormat::Text);
+        assert_eq!(format(&["-o", "resultados.dat"]), OutputFormat::Text);
+        // --format explícito prevalece sobre a extensão
+        assert_eq!(
+            format(&["-o", "resultados.json", "--format", "json-array"]),
+            OutputFormat::JsonArray
+        );
+        assert_eq!(format(&[]), OutputFormat::Text);
     }
 
     #[test]
-    fn test_generate_combinations_2_digits_alphanumeric() {
-        let combos = generate_combinations(2, false, false);
-        assert_eq!(combos.len(), 1296); // 36^2
-        assert!(combos.contains(&"aa".to_string()));
-        assert!(combos.contains(&"00".to_string()));
-        assert!(combos.contains(&"a1".to_string()));
-        assert!(combos.contains(&"z9".to_string()));
+    fn test_config_file_precedence() {
+        let path = std::env::temp_dir().join("registrobr-finder-test-config.toml");
+        fs::write(
+            &path,
+            "workers = 50\ntimeout = 3\nsuffix = [\".net.br\", \".com.br\"]\nletters = true\nmax-rps = 2.5\n",
+        )
+        .unwrap();
+
+        let args = parse([
+            "registrobr-finder",
+            "--config",
+            path.to_str().unwrap(),
+            "--workers",
+            "5",
+        ])
+        .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(args.workers, Workers::Fixed(5));
+        assert_eq!(args.timeout, 3);
+        assert_eq!(args.suffix, [".net.br", ".com.br"]);
+        assert!(args.letters);
+        assert_eq!(args.max_rps, Some(2.5));
+        assert_eq!(args.retries, 3);
     }
 
     #[test]
-    fn test_generate_combinations_3_digits_numbers_only() {
-        let combos = generate_combinations(3, false, true);
-        assert_eq!(combos.len(), 1000); // 10^3
-        assert!(combos.contains(&"000".to_string()));
-        assert!(combos.contains(&"999".to_string()));
-        assert!(combos.contains(&"123".to_string()));
+    fn test_environment_variables_between_cli_and_config_file() {
+        let env = EnvGuard::set(&[
+            ("REGISTROBR_RETRY_DELAY", "250"),
+            ("REGISTROBR_CACHE_TTL", "60"),
+        ]);
+        let path = std::env::temp_dir().join("registrobr-finder-test-env.toml");
+        fs::write(&path, "retry-delay = 900\ncache-ttl = 30\n").unwrap();
+
+        let args = parse_args([
+            "registrobr-finder",
+            "--config",
+            path.to_str().unwrap(),
+            "--cache-ttl",
+            "10",
+        ]);
+        drop(env);
+        fs::remove_file(&path).unwrap();
+
+        let args = args.unwrap();
+        assert_eq!(args.retry_delay, 250);
+        assert_eq!(args.cache_ttl, 10);
     }
 
     #[test]
-    fn test_generate_combinations_3_digits_letters_only() {
-        let combos = generate_combinations(3, true, false);
-        assert_eq!(combos.len(), 17576); // 26^3
-        assert!(combos.contains(&"aaa".to_string()));
-        assert!(combos.contains(&"zzz".to_string()));
-        assert!(combos.contains(&"abc".to_string()));
+    fn test_config_file_yields_to_conflicting_cli_option() {
+        let path = std::env::temp_dir().join("registrobr-finder-test-config-conflict.toml");
+        fs::write(&path, "digits = 4\ncheck = \"loja\"\n").unwrap();
+
+        let args = parse([
+            "registrobr-finder",
+            "--config",
+            path.to_str().unwrap(),
+            "--min-length",
+            "3",
+            "--input",
+            "nomes.txt",
+        ]);
+        fs::remove_file(&path).unwrap();
+
+        let args = args.unwrap();
+        assert_eq!(args.digits, None);
+        assert_eq!(args.min_length, Some(3));
+        assert_eq!(args.check, None);
+        assert_eq!(args.input.as_deref(), Some("nomes.txt"));
     }
 
     #[test]
-    fn test_generate_combinations_3_digits_alphanumeric() {
-        let combos = generate_combinations(3, false, false);
-        assert_eq!(combos.len(), 46656); // 36^3
+    fn test_config_file_rejects_unknown_option() {
+        let path = std::env::temp_dir().join("registrobr-finder-test-config-unknown.toml");
+        fs::write(&path, "wrokers = 5\n").unwrap();
+
+        let result = parse(["registrobr-finder", "--config", path.to_str().unwrap()]);
+        fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
     }
 }
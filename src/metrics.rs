@@ -0,0 +1,233 @@
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::DomainResult;
+
+/// Espera depois de uma falha no accept (como EMFILE), para não girar em falso
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Limites superiores (em segundos) dos buckets do histograma de latência
+const LATENCY_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Métricas compartilhadas entre os workers
+pub type SharedMetrics = Arc<Metrics>;
+
+/// Contadores da varredura no formato de texto do Prometheus, alimentados pelo
+/// [`Checker`](crate::Checker) e servidos por [`serve`]
+#[derive(Debug, Default)]
+pub struct Metrics {
+    checks: AtomicU64,
+    available: AtomicU64,
+    errors: AtomicU64,
+    rate_limited: AtomicU64,
+    /// Consultas à API por bucket de [`LATENCY_BUCKETS`], sem acumular; o último é o
+    /// `+Inf`
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+    latency_sum_micros: AtomicU64,
+}
+
+impl Metrics {
+    pub fn shared() -> SharedMetrics {
+        Arc::new(Self::default())
+    }
+
+    /// Conta um domínio verificado; a latência só entra no histograma quando houve
+    /// consulta à API (resultados do cache ou do DNS têm latência zero)
+    pub fn record(&self, result: &DomainResult) {
+        self.checks.fetch_add(1, Ordering::Relaxed);
+        if result.available {
+            self.available.fetch_add(1, Ordering::Relaxed);
+        }
+        if result.error.is_some() {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        if !result.latency.is_zero() {
+            self.observe_latency(result.latency);
+        }
+    }
+
+    /// Conta uma resposta 429, inclusive as que ainda serão tentadas de novo
+    pub fn record_rate_limited(&self) {
+        self.rate_limited.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn observe_latency(&self, latency: Duration) {
+        let secs = latency.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|&le| secs <= le)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.latency_sum_micros
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Métricas no formato de exposição em texto do Prometheus
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let counters = [
+            ("checks_total", "Domínios verificados", &self.checks),
+            (
+                "available_total",
+                "Domínios disponíveis encontrados",
+                &self.available,
+            ),
+            ("errors_total", "Verificações que falharam", &self.errors),
+            (
+                "rate_limited_total",
+                "Respostas 429 recebidas da API",
+                &self.rate_limited,
+            ),
+        ];
+        for (name, help, counter) in counters {
+            let _ = writeln!(out, "# HELP registrobr_finder_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE registrobr_finder_{} counter", name);
+            let _ = writeln!(
+                out,
+                "registrobr_finder_{} {}",
+                name,
+                counter.load(Ordering::Relaxed)
+            );
+        }
+
+        let name = "registrobr_finder_query_duration_seconds";
+        let _ = writeln!(out, "# HELP {} Duração das consultas à API", name);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        let mut cumulative = 0;
+        for (i, bucket) in self.latency_buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let le = LATENCY_BUCKETS
+                .get(i)
+                .map_or("+Inf".to_string(), |le| le.to_string());
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, le, cumulative);
+        }
+        let sum = self.latency_sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(out, "{}_sum {}", name, sum);
+        let _ = writeln!(out, "{}_count {}", name, cumulative);
+        out
+    }
+}
+
+/// Atende `GET /metrics` em `listener` numa tarefa em segundo plano até ela ser
+/// abortada. Qualquer outro caminho recebe 404.
+pub fn serve(listener: TcpListener, metrics: SharedMetrics) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    debug!(error = %e, "falha ao aceitar conexão de métricas");
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    continue;
+                }
+            };
+            let metrics = metrics.clone();
+            tokio::spawn(async move {
+                if let Err(e) = respond(stream, &metrics).await {
+                    debug!(%peer, error = %e, "falha ao responder a requisição de métricas");
+                }
+            });
+        }
+    })
+}
+
+async fn respond(mut stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+    // Só a linha de requisição importa; o restante dos cabeçalhos é ignorado
+    let mut buffer = [0; 1024];
+    let read = stream.read(&mut buffer).await?;
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            metrics.render(),
+        ),
+        _ => ("404 Not Found", "text/plain; charset=utf-8", String::new()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counters_and_histogram() {
+        let metrics = Metrics::default();
+        metrics.record(&DomainResult {
+            domain: "loja.com.br".to_string(),
+            available: true,
+            latency: Duration::from_millis(80),
+            ..Default::default()
+        });
+        metrics.record(&DomainResult {
+            latency: Duration::from_secs(3),
+            ..DomainResult::failed("teste.com.br".to_string(), "timeout")
+        });
+        // Vindo do cache: conta como verificado, mas não entra no histograma
+        metrics.record(&DomainResult {
+            domain: "cache.com.br".to_string(),
+            ..Default::default()
+        });
+        metrics.record_rate_limited();
+
+        let text = metrics.render();
+        assert!(text.contains("registrobr_finder_checks_total 3\n"));
+        assert!(text.contains("registrobr_finder_available_total 1\n"));
+        assert!(text.contains("registrobr_finder_errors_total 1\n"));
+        assert!(text.contains("registrobr_finder_rate_limited_total 1\n"));
+        assert!(text.contains("registrobr_finder_query_duration_seconds_bucket{le=\"0.05\"} 0\n"));
+        assert!(text.contains("registrobr_finder_query_duration_seconds_bucket{le=\"0.1\"} 1\n"));
+        assert!(text.contains("registrobr_finder_query_duration_seconds_bucket{le=\"2.5\"} 1\n"));
+        assert!(text.contains("registrobr_finder_query_duration_seconds_bucket{le=\"5\"} 2\n"));
+        assert!(text.contains("registrobr_finder_query_duration_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(text.contains("registrobr_finder_query_duration_seconds_sum 3.08\n"));
+        assert!(text.contains("registrobr_finder_query_duration_seconds_count 2\n"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_metrics_endpoint() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let metrics = Metrics::shared();
+        metrics.record_rate_limited();
+        let server = serve(listener, metrics);
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(format!("http://{}/metrics", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(response
+            .text()
+            .await
+            .unwrap()
+            .contains("registrobr_finder_rate_limited_total 1"));
+
+        let missing = client
+            .get(format!("http://{}/", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), 404);
+        server.abort();
+    }
+}
@@ -0,0 +1,25 @@
+use reqwest::{RequestBuilder, Response};
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Gancho em torno de cada requisição feita pelo [`Checker`](crate::Checker), para
+/// acrescentar cabeçalhos (autenticação, rastreamento) ou observar as respostas sem
+/// trocar o cliente HTTP. Registrado com
+/// [`Checker::with_middleware`](crate::Checker::with_middleware); com vários, eles
+/// são chamados na ordem em que foram registrados.
+///
+/// Os ganchos valem para cada requisição enviada, inclusive novas tentativas e
+/// requisições de reserva do hedging, mas não para resultados vindos do cache ou do
+/// pré-filtro de DNS.
+pub trait Middleware: Debug + Send + Sync {
+    /// Ajusta a requisição antes do envio; o User-Agent já está definido
+    fn on_request(&self, request: RequestBuilder) -> RequestBuilder {
+        request
+    }
+
+    /// Chamado com cada resposta recebida, antes de ela ser interpretada (inclusive
+    /// as 429), junto com o tempo desde o envio
+    fn on_response(&self, response: &Response, elapsed: Duration) {
+        let _ = (response, elapsed);
+    }
+}
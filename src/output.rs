@@ -0,0 +1,396 @@
+use anyhow::Result;
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{ContentArrangement, Table};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+use crate::DomainResult;
+
+/// Escreve os resultados em CSV, com cabeçalho
+/// `domain,available,status,error,publication_status,unicode,expires_in_days,restriction,status_code`
+pub fn write_csv<W: Write>(writer: W, results: &[DomainResult]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for result in results {
+        writer.serialize(result)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Como [`write_csv`], mas sem o cabeçalho, para estender um arquivo que já o tem
+pub fn append_csv<W: Write>(writer: W, results: &[DomainResult]) -> Result<()> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(writer);
+    for result in results {
+        writer.serialize(result)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Escreve os resultados como um array JSON seguido de quebra de linha; com `pretty`,
+/// indentado para leitura
+pub fn write_json<W: Write>(writer: W, results: &[DomainResult], pretty: bool) -> Result<()> {
+    write_json_value(writer, &results, pretty)
+}
+
+fn write_json_value<W: Write, T: Serialize>(mut writer: W, value: &T, pretty: bool) -> Result<()> {
+    if pretty {
+        serde_json::to_writer_pretty(&mut writer, value)?;
+    } else {
+        serde_json::to_writer(&mut writer, value)?;
+    }
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Relatório de `--format json`: os resultados seguidos dos totais da varredura
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct JsonReport<'a> {
+    pub results: &'a [DomainResult],
+    /// Domínios verificados, inclusive os que um filtro deixou de fora de `results`
+    pub total: usize,
+    pub available_count: usize,
+    pub error_count: usize,
+    /// Início da varredura, em RFC 3339
+    pub started_at: &'a str,
+    pub elapsed_secs: f64,
+}
+
+/// Escreve o relatório como um objeto JSON seguido de quebra de linha; com `pretty`,
+/// indentado para leitura
+pub fn write_json_report<W: Write>(writer: W, report: &JsonReport, pretty: bool) -> Result<()> {
+    write_json_value(writer, report, pretty)
+}
+
+/// Escreve o relatório em YAML, com os mesmos campos de [`write_json_report`]: os
+/// resultados seguidos dos totais
+pub fn write_yaml_report<W: Write>(mut writer: W, report: &JsonReport) -> Result<()> {
+    serde_yaml::to_writer(&mut writer, report)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Formatos aceitos ao ler um JSON (ou YAML) gravado anteriormente
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonResults {
+    Report { results: Vec<DomainResult> },
+    Array(Vec<DomainResult>),
+}
+
+/// Lê os resultados de um relatório de [`write_json_report`] ou de um array de
+/// [`write_json`]
+pub fn parse_json_results(content: &str) -> serde_json::Result<Vec<DomainResult>> {
+    Ok(match serde_json::from_str(content)? {
+        JsonResults::Report { results } | JsonResults::Array(results) => results,
+    })
+}
+
+/// Lê os resultados de um relatório de [`write_yaml_report`]
+pub fn parse_yaml_results(content: &str) -> serde_yaml::Result<Vec<DomainResult>> {
+    Ok(match serde_yaml::from_str(content)? {
+        JsonResults::Report { results } | JsonResults::Array(results) => results,
+    })
+}
+
+/// Escreve um resultado como uma linha JSON (NDJSON) e descarrega o writer, para
+/// que consumidores em streaming o recebam assim que ele é conhecido
+pub fn write_ndjson_line<W: Write>(mut writer: W, result: &DomainResult) -> Result<()> {
+    let mut line = serde_json::to_vec(result)?;
+    line.push(b'\n');
+    writer.write_all(&line)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Escreve os resultados como uma tabela com bordas (domínio, status e expiração),
+/// com as colunas ajustadas ao conteúdo e, em um terminal, à largura dele
+pub fn write_table<W: Write>(mut writer: W, results: &[DomainResult]) -> Result<()> {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL_CONDENSED)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(["Domínio", "Status", "Expiração"]);
+    for result in results {
+        // O status vai inteiro, como no CSV e no JSON: a marca do DNS e o
+        // publication-status fazem parte dele
+        let status = match result.error {
+            Some(ref error) => format!("erro: {}", error),
+            None => {
+                let status = result.status.clone().unwrap_or_else(|| "-".to_string());
+                match result.restriction {
+                    Some(ref restriction) => format!("{} (restrito: {})", status, restriction),
+                    None => status,
+                }
+            }
+        };
+        table.add_row([
+            result.display_name(),
+            status,
+            result.expiry_date().unwrap_or("-").to_string(),
+        ]);
+    }
+    writeln!(writer, "{}", table)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_json_round_trip() {
+        let results = vec![
+            DomainResult {
+                domain: "loja.com.br".to_string(),
+                available: true,
+                status: Some("disponível".to_string()),
+                error: None,
+                ..Default::default()
+            },
+            DomainResult {
+                domain: "teste.com.br".to_string(),
+                available: false,
+                status: None,
+                error: Some("rate limited".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        let mut buffer = Vec::new();
+        write_json(&mut buffer, &results, false).unwrap();
+        assert_eq!(buffer.iter().filter(|&&b| b == b'\n').count(), 1);
+        let parsed: Vec<DomainResult> = serde_json::from_slice(&buffer).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].domain, "loja.com.br");
+        assert!(parsed[0].available);
+        assert_eq!(parsed[0].status.as_deref(), Some("disponível"));
+        assert_eq!(parsed[1].error.as_deref(), Some("rate limited"));
+    }
+
+    #[test]
+    fn test_write_json_report() {
+        let results = vec![DomainResult {
+            domain: "loja.com.br".to_string(),
+            available: true,
+            ..Default::default()
+        }];
+        let report = JsonReport {
+            results: &results,
+            total: 3,
+            available_count: 1,
+            error_count: 1,
+            started_at: "2026-01-01T12:00:00Z",
+            elapsed_secs: 1.5,
+        };
+
+        let mut buffer = Vec::new();
+        write_json_report(&mut buffer, &report, false).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(value["total"], 3);
+        assert_eq!(value["available_count"], 1);
+        assert_eq!(value["error_count"], 1);
+        assert_eq!(value["started_at"], "2026-01-01T12:00:00Z");
+        assert_eq!(value["results"][0]["domain"], "loja.com.br");
+
+        let parsed = parse_json_results(std::str::from_utf8(&buffer).unwrap()).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert!(parsed[0].available);
+    }
+
+    #[test]
+    fn test_yaml_report_round_trip() {
+        let results = vec![
+            DomainResult {
+                domain: "xn--caf-dma.com.br".to_string(),
+                available: true,
+                status: Some("disponível".to_string()),
+                unicode: Some("café.com.br".to_string()),
+                ..Default::default()
+            },
+            DomainResult {
+                domain: "loja.com.br".to_string(),
+                status: Some("registrado (expira: 2026-01-01)".to_string()),
+                expires_in_days: Some(-3),
+                ..Default::default()
+            },
+            DomainResult::failed("teste.com.br".to_string(), "parse error: \"x\": y"),
+        ];
+        let report = JsonReport {
+            results: &results,
+            total: 3,
+            available_count: 1,
+            error_count: 1,
+            started_at: "2026-01-01T12:00:00Z",
+            elapsed_secs: 1.5,
+        };
+
+        let mut buffer = Vec::new();
+        write_yaml_report(&mut buffer, &report).unwrap();
+        let yaml = String::from_utf8(buffer).unwrap();
+        assert!(yaml.starts_with("results:\n"));
+        assert!(yaml.contains("\ntotal: 3\n"));
+
+        let parsed = parse_yaml_results(&yaml).unwrap();
+        assert_eq!(parsed.len(), results.len());
+        for (parsed, original) in parsed.iter().zip(&results) {
+            assert_eq!(parsed.domain, original.domain);
+            assert_eq!(parsed.available, original.available);
+            assert_eq!(parsed.status, original.status);
+            assert_eq!(parsed.error, original.error);
+            assert_eq!(parsed.unicode, original.unicode);
+            assert_eq!(parsed.expires_in_days, original.expires_in_days);
+        }
+    }
+
+    #[test]
+    fn test_parse_json_results_accepts_flat_array() {
+        let mut buffer = Vec::new();
+        write_json(
+            &mut buffer,
+            &[DomainResult::failed("loja.com.br".to_string(), "HTTP 500")],
+            true,
+        )
+        .unwrap();
+        assert!(std::str::from_utf8(&buffer).unwrap().contains("\n  {\n"));
+
+        let parsed = parse_json_results(std::str::from_utf8(&buffer).unwrap()).unwrap();
+        assert_eq!(parsed[0].error.as_deref(), Some("HTTP 500"));
+        assert!(parse_json_results("{\"total\": 1}").is_err());
+    }
+
+    #[test]
+    fn test_write_ndjson_lines() {
+        let mut buffer = Vec::new();
+        for domain in ["loja.com.br", "teste.com.br"] {
+            let result = DomainResult {
+                domain: domain.to_string(),
+                available: true,
+                ..Default::default()
+            };
+            write_ndjson_line(&mut buffer, &result).unwrap();
+        }
+
+        let text = String::from_utf8(buffer).unwrap();
+        let parsed: Vec<DomainResult> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(text.lines().count(), 2);
+        assert_eq!(parsed[1].domain, "teste.com.br");
+    }
+
+    #[test]
+    fn test_write_csv_round_trip() {
+        let results = vec![
+            DomainResult {
+                domain: "loja.com.br".to_string(),
+                available: false,
+                status: Some("registrado (expira: 2026-01-01)".to_string()),
+                error: None,
+                publication_status: Some("published".to_string()),
+                ..Default::default()
+            },
+            DomainResult {
+                domain: "teste.com.br".to_string(),
+                available: false,
+                status: None,
+                error: Some("parse error: expected value, line 1".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        let mut buffer = Vec::new();
+        write_csv(&mut buffer, &results).unwrap();
+        let csv_text = String::from_utf8(buffer).unwrap();
+        assert!(csv_text.starts_with(
+            "domain,available,status,error,publication_status,unicode,expires_in_days,restriction,status_code\n"
+        ));
+        assert!(csv_text.contains("\"parse error: expected value, line 1\""));
+
+        let parsed: Vec<DomainResult> = csv::Reader::from_reader(csv_text.as_bytes())
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].status, results[0].status);
+        assert_eq!(parsed[0].error, None);
+        assert_eq!(parsed[0].publication_status, results[0].publication_status);
+        assert_eq!(parsed[1].status, None);
+        assert_eq!(parsed[1].error, results[1].error);
+        assert_eq!(parsed[1].publication_status, None);
+    }
+
+    #[test]
+    fn test_append_csv_extends_without_header() {
+        let result = |domain: &str| DomainResult {
+            domain: domain.to_string(),
+            available: true,
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        write_csv(&mut buffer, &[result("loja.com.br")]).unwrap();
+        append_csv(&mut buffer, &[result("teste.com.br")]).unwrap();
+
+        let parsed: Vec<DomainResult> = csv::Reader::from_reader(buffer.as_slice())
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let domains: Vec<_> = parsed.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(domains, ["loja.com.br", "teste.com.br"]);
+    }
+
+    #[test]
+    fn test_write_table() {
+        let results = vec![
+            DomainResult {
+                domain: "xn--caf-dma.com.br".to_string(),
+                available: true,
+                status: Some("disponível".to_string()),
+                unicode: Some("café.com.br".to_string()),
+                ..Default::default()
+            },
+            DomainResult {
+                domain: "loja.com.br".to_string(),
+                status: Some("registrado (expira: 2026-01-01)".to_string()),
+                ..Default::default()
+            },
+            DomainResult {
+                domain: "dns.com.br".to_string(),
+                status: Some(crate::DNS_REGISTERED_STATUS.to_string()),
+                ..Default::default()
+            },
+            DomainResult {
+                domain: "fila.com.br".to_string(),
+                status: Some("em processo (waiting)".to_string()),
+                publication_status: Some("waiting".to_string()),
+                ..Default::default()
+            },
+            DomainResult::failed("teste.com.br".to_string(), "HTTP 500"),
+        ];
+
+        let mut buffer = Vec::new();
+        write_table(&mut buffer, &results).unwrap();
+        let table = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert!(lines[0].starts_with('┌'));
+        assert!(lines[1].contains("Domínio") && lines[1].contains("Expiração"));
+        let row = |domain: &str| *lines.iter().find(|line| line.contains(domain)).unwrap();
+        assert!(row("café.com.br").contains("disponível"));
+        assert!(row("loja.com.br").contains("registrado (expira: 2026-01-01)"));
+        assert!(row("loja.com.br").contains("┆ 2026-01-01"));
+        assert!(row("dns.com.br").contains(crate::DNS_REGISTERED_STATUS));
+        assert!(row("fila.com.br").contains("em processo (waiting)"));
+        assert!(row("teste.com.br").contains("erro: HTTP 500"));
+        // Todas as linhas da tabela têm a mesma largura
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::checker::RATE_LIMITED_ERROR;
+use crate::{DomainResult, StatusKind};
+
+/// Quantos resultados caíram em cada categoria de status, para o resumo
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatusBreakdown {
+    pub available: usize,
+    pub registered: usize,
+    pub in_process: usize,
+    pub release: usize,
+    pub unavailable: usize,
+    pub unknown: usize,
+    /// Códigos de status não documentados que apareceram, em ordem e sem repetição
+    pub unknown_codes: Vec<i32>,
+    /// Erros por 429 persistente, separados dos outros para mostrar o rate limiting
+    pub rate_limited: usize,
+    pub other_errors: usize,
+}
+
+impl StatusBreakdown {
+    pub fn from_results(results: &[DomainResult]) -> Self {
+        let mut breakdown = Self::default();
+        for result in results {
+            let count = match result.kind() {
+                StatusKind::Available => &mut breakdown.available,
+                StatusKind::Registered => &mut breakdown.registered,
+                StatusKind::InProcess => &mut breakdown.in_process,
+                StatusKind::Release => &mut breakdown.release,
+                StatusKind::Unavailable => &mut breakdown.unavailable,
+                StatusKind::Unknown => &mut breakdown.unknown,
+                StatusKind::Error if result.error.as_deref() == Some(RATE_LIMITED_ERROR) => {
+                    &mut breakdown.rate_limited
+                }
+                StatusKind::Error => &mut breakdown.other_errors,
+            };
+            *count += 1;
+            if let (StatusKind::Unknown, Some(code)) = (result.kind(), result.status_code) {
+                breakdown.unknown_codes.push(code);
+            }
+        }
+        breakdown.unknown_codes.sort_unstable();
+        breakdown.unknown_codes.dedup();
+        breakdown
+    }
+}
+
+/// Estatísticas de latência das consultas feitas à API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// Número de consultas consideradas
+    pub count: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl LatencyStats {
+    /// Calcula as estatísticas sobre os resultados que foram consultados na API;
+    /// resultados vindos do cache ou do DNS (latência zero) ficam de fora
+    pub fn from_results(results: &[DomainResult]) -> Option<Self> {
+        let mut latencies: Vec<Duration> = results
+            .iter()
+            .map(|r| r.latency)
+            .filter(|latency| !latency.is_zero())
+            .collect();
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_unstable();
+
+        Some(Self {
+            count: latencies.len(),
+            min: latencies[0],
+            median: percentile(&latencies, 50),
+            p95: percentile(&latencies, 95),
+            max: latencies[latencies.len() - 1],
+        })
+    }
+}
+
+/// Percentil pelo método do posto mais próximo; `sorted` não pode ser vazio
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Vazão recente medida numa janela deslizante. Diferente da média desde o início,
+/// acompanha as mudanças de ritmo do limitador adaptativo (backoff após 429,
+/// recuperação gradual), então serve de base para estimar o tempo restante.
+#[derive(Debug, Clone)]
+pub struct RecentRate {
+    window: Duration,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl RecentRate {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Registra que `done` itens estavam concluídos em `now`; amostras mais antigas
+    /// que a janela são descartadas
+    pub fn record(&mut self, now: Instant, done: u64) {
+        self.samples.push_back((now, done));
+        while let Some(&(at, _)) = self.samples.front() {
+            if now.duration_since(at) <= self.window {
+                break;
+            }
+            self.samples.pop_front();
+        }
+    }
+
+    /// Itens por segundo entre a amostra mais antiga e a mais recente da janela
+    pub fn rate(&self) -> f64 {
+        let (Some(&(since, first)), Some(&(until, last))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return 0.0;
+        };
+        let elapsed = until.duration_since(since).as_secs_f64();
+        if elapsed > 0.0 {
+            last.saturating_sub(first) as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    /// Tempo para concluir `remaining` itens no ritmo recente; `None` enquanto não
+    /// houver vazão medida
+    pub fn eta(&self, remaining: u64) -> Option<Duration> {
+        let rate = self.rate();
+        (rate > 0.0).then(|| Duration::from_secs_f64(remaining as f64 / rate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(latency_ms: u64) -> DomainResult {
+        DomainResult {
+            domain: "loja.com.br".to_string(),
+            latency: Duration::from_millis(latency_ms),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_status_breakdown() {
+        let status = |status: &str| DomainResult {
+            status: Some(status.to_string()),
+            ..Default::default()
+        };
+        let results = [
+            DomainResult {
+                available: true,
+                ..status("disponível")
+            },
+            status("registrado (expira: 2027-01-01)"),
+            status(crate::DNS_REGISTERED_STATUS),
+            status("em processo (waiting)"),
+            status("em liberação (release-process)"),
+            status("indisponível"),
+            DomainResult {
+                status_code: Some(7),
+                ..status("status desconhecido (7)")
+            },
+            DomainResult {
+                status_code: Some(1),
+                ..status("status desconhecido (1)")
+            },
+            DomainResult {
+                status_code: Some(7),
+                ..status("status desconhecido (7)")
+            },
+            DomainResult::failed("a.com.br".to_string(), RATE_LIMITED_ERROR),
+            DomainResult::failed("b.com.br".to_string(), RATE_LIMITED_ERROR),
+            DomainResult::failed("c.com.br".to_string(), "timeout"),
+        ];
+        assert_eq!(
+            StatusBreakdown::from_results(&results),
+            StatusBreakdown {
+                available: 1,
+                registered: 2,
+                in_process: 1,
+                release: 1,
+                unavailable: 1,
+                unknown: 3,
+                unknown_codes: vec![1, 7],
+                rate_limited: 2,
+                other_errors: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let results: Vec<_> = (1..=20).rev().map(|i| result(i * 10)).collect();
+        let stats = LatencyStats::from_results(&results).unwrap();
+
+        assert_eq!(stats.count, 20);
+        assert_eq!(stats.min, Duration::from_millis(10));
+        assert_eq!(stats.median, Duration::from_millis(100));
+        assert_eq!(stats.p95, Duration::from_millis(190));
+        assert_eq!(stats.max, Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_latency_stats_ignores_cached_results() {
+        assert_eq!(LatencyStats::from_results(&[result(0)]), None);
+
+        let stats = LatencyStats::from_results(&[result(0), result(30)]).unwrap();
+        assert_eq!(stats.count, 1);
+        assert_eq!(stats.median, Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_recent_rate_follows_window() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut rate = RecentRate::new(Duration::from_secs(10));
+        assert_eq!(rate.eta(100), None);
+
+        // 10 itens/s nos primeiros 10 s
+        rate.record(at(0), 0);
+        rate.record(at(10), 100);
+        assert_eq!(rate.rate(), 10.0);
+        assert_eq!(rate.eta(50), Some(Duration::from_secs(5)));
+
+        // Depois de um backoff o ritmo cai para 1 item/s e a janela esquece o anterior
+        rate.record(at(20), 110);
+        assert_eq!(rate.rate(), 1.0);
+        assert_eq!(rate.eta(50), Some(Duration::from_secs(50)));
+
+        // Sem progresso na janela não há estimativa
+        rate.record(at(31), 110);
+        assert_eq!(rate.eta(50), None);
+    }
+}
@@ -0,0 +1,339 @@
+//! Categorias de segundo nível sob .br abertas a registro.
+//!
+//! A lista segue as categorias publicadas pelo Registro.br e é mantida à mão; as
+//! de uso restrito a órgãos públicos (`.gov.br`, `.mil.br`, `.jus.br` etc.) ficam
+//! de fora.
+
+use anyhow::{anyhow, bail, Result};
+
+/// Grupo de sufixos, como o Registro.br os organiza
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SuffixCategory {
+    /// Uso geral: `.com.br`, `.net.br`, `.app.br`...
+    Generic,
+    /// Atividades de empresas: `.ind.br`, `.inf.br`, `.tur.br`...
+    Business,
+    /// Profissionais liberais: `.adv.br`, `.eng.br`, `.med.br`...
+    Professional,
+    /// Pessoas físicas: `.blog.br`, `.nom.br`...
+    Personal,
+    /// Cidades: `.rio.br`, `.sampa.br`, `.poa.br`...
+    City,
+}
+
+use SuffixCategory::*;
+
+/// Sufixos conhecidos e suas categorias
+pub const KNOWN_SUFFIXES: &[(&str, SuffixCategory)] = &[
+    (".com.br", Generic),
+    (".net.br", Generic),
+    (".org.br", Generic),
+    (".ong.br", Generic),
+    (".app.br", Generic),
+    (".dev.br", Generic),
+    (".art.br", Generic),
+    (".eco.br", Generic),
+    (".emp.br", Generic),
+    (".log.br", Generic),
+    (".seg.br", Generic),
+    (".tec.br", Generic),
+    (".agr.br", Business),
+    (".am.br", Business),
+    (".coop.br", Business),
+    (".esp.br", Business),
+    (".etc.br", Business),
+    (".far.br", Business),
+    (".fm.br", Business),
+    (".imb.br", Business),
+    (".ind.br", Business),
+    (".inf.br", Business),
+    (".radio.br", Business),
+    (".rec.br", Business),
+    (".srv.br", Business),
+    (".tmp.br", Business),
+    (".tur.br", Business),
+    (".tv.br", Business),
+    (".adm.br", Professional),
+    (".adv.br", Professional),
+    (".arq.br", Professional),
+    (".ato.br", Professional),
+    (".bib.br", Professional),
+    (".bio.br", Professional),
+    (".bmd.br", Professional),
+    (".cim.br", Professional),
+    (".cng.br", Professional),
+    (".cnt.br", Professional),
+    (".des.br", Professional),
+    (".det.br", Professional),
+    (".ecn.br", Professional),
+    (".enf.br", Professional),
+    (".eng.br", Professional),
+    (".eti.br", Professional),
+    (".fnd.br", Professional),
+    (".fot.br", Professional),
+    (".fst.br", Professional),
+    (".geo.br", Professional),
+    (".ggf.br", Professional),
+    (".jor.br", Professional),
+    (".lel.br", Professional),
+    (".mat.br", Professional),
+    (".med.br", Professional),
+    (".mus.br", Professional),
+    (".not.br", Professional),
+    (".ntr.br", Professional),
+    (".odo.br", Professional),
+    (".ppg.br", Professional),
+    (".pro.br", Professional),
+    (".psc.br", Professional),
+    (".psi.br", Professional),
+    (".qsl.br", Professional),
+    (".rep.br", Professional),
+    (".slg.br", Professional),
+    (".taxi.br", Professional),
+    (".teo.br", Professional),
+    (".trd.br", Professional),
+    (".vet.br", Professional),
+    (".zlg.br", Professional),
+    (".blog.br", Personal),
+    (".flog.br", Personal),
+    (".nom.br", Personal),
+    (".vlog.br", Personal),
+    (".wiki.br", Personal),
+    (".belem.br", City),
+    (".bhz.br", City),
+    (".boavista.br", City),
+    (".bsb.br", City),
+    (".campinagrande.br", City),
+    (".campinas.br", City),
+    (".caxias.br", City),
+    (".curitiba.br", City),
+    (".floripa.br", City),
+    (".fortal.br", City),
+    (".foz.br", City),
+    (".goiania.br", City),
+    (".jampa.br", City),
+    (".joinville.br", City),
+    (".londrina.br", City),
+    (".macapa.br", City),
+    (".maceio.br", City),
+    (".manaus.br", City),
+    (".maringa.br", City),
+    (".morena.br", City),
+    (".natal.br", City),
+    (".niteroi.br", City),
+    (".osasco.br", City),
+    (".palmas.br", City),
+    (".poa.br", City),
+    (".pvh.br", City),
+    (".recife.br", City),
+    (".ribeirao.br", City),
+    (".rio.br", City),
+    (".riobranco.br", City),
+    (".salvador.br", City),
+    (".sampa.br", City),
+    (".santamaria.br", City),
+    (".santoandre.br", City),
+    (".saobernardo.br", City),
+    (".saogonca.br", City),
+    (".sjc.br", City),
+    (".slz.br", City),
+    (".sorocaba.br", City),
+    (".the.br", City),
+    (".vix.br", City),
+];
+
+/// Sufixos cujo registro exige comprovação (registro em conselho profissional,
+/// outorga de radiodifusão etc.) e o que é pedido. A API informa só se o nome está
+/// livre, então um domínio disponível nesses sufixos pode não estar ao alcance de
+/// qualquer um.
+pub const RESTRICTED_SUFFIXES: &[(&str, &str)] = &[
+    (".adm.br", "administradores com registro no CRA"),
+    (".adv.br", "advogados com registro na OAB"),
+    (".arq.br", "arquitetos com registro no CAU"),
+    (".cnt.br", "contadores com registro no CRC"),
+    (".enf.br", "enfermeiros com registro no COREN"),
+    (".eng.br", "engenheiros com registro no CREA"),
+    (".med.br", "médicos com registro no CRM"),
+    (".odo.br", "dentistas com registro no CRO"),
+    (".psi.br", "psicólogos com registro no CRP"),
+    (".vet.br", "veterinários com registro no CRMV"),
+    (".coop.br", "cooperativas"),
+    (".am.br", "emissoras de rádio AM"),
+    (".fm.br", "emissoras de rádio FM"),
+    (".radio.br", "emissoras de rádio"),
+    (".tv.br", "emissoras de televisão"),
+];
+
+/// Restrição de registro de `suffix`, se houver (ver [`RESTRICTED_SUFFIXES`])
+pub fn restriction(suffix: &str) -> Option<&'static str> {
+    RESTRICTED_SUFFIXES
+        .iter()
+        .find(|(restricted, _)| *restricted == suffix)
+        .map(|(_, note)| *note)
+}
+
+/// Sufixos das categorias escolhidas, na ordem de [`KNOWN_SUFFIXES`]; sem categorias,
+/// todos eles
+pub fn known_suffixes(categories: &[SuffixCategory]) -> Vec<String> {
+    KNOWN_SUFFIXES
+        .iter()
+        .filter(|(_, category)| categories.is_empty() || categories.contains(category))
+        .map(|(suffix, _)| suffix.to_string())
+        .collect()
+}
+
+/// Normaliza um sufixo de --suffix: remove espaços nas pontas, passa para minúsculas
+/// e acrescenta o ponto inicial quando falta (`com.br` vira `.com.br`, e não
+/// `lojacom.br`). O resultado precisa terminar em `.br` e ter só partes válidas
+/// (letras, dígitos e hífens, sem hífen no início ou no fim).
+pub fn normalize_suffix(suffix: &str) -> Result<String> {
+    let trimmed = suffix.trim().to_lowercase();
+    let normalized = if trimmed.starts_with('.') {
+        trimmed
+    } else {
+        format!(".{}", trimmed)
+    };
+    if !normalized.ends_with(".br") {
+        bail!("sufixo \"{}\" não termina em .br", suffix.trim());
+    }
+    let valid_parts = normalized[1..].split('.').all(|part| {
+        !part.is_empty()
+            && !part.starts_with('-')
+            && !part.ends_with('-')
+            && part
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    });
+    if !valid_parts {
+        bail!(
+            "sufixo inválido \"{}\" (esperado algo como .com.br)",
+            suffix.trim()
+        );
+    }
+    Ok(normalized)
+}
+
+/// Lê uma lista de sufixos com um por linha (--suffix-file), ignorando linhas vazias,
+/// comentários (#) e repetidos. Cada sufixo precisa começar com ponto e terminar em
+/// `.br`; o ponto inicial não é acrescentado para não esconder um erro de digitação.
+/// Fora isso, valem as regras de [`normalize_suffix`].
+pub fn parse_suffix_list(content: &str) -> Result<Vec<String>> {
+    let mut suffixes: Vec<String> = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let suffix = line.trim().to_lowercase();
+        if suffix.is_empty() || suffix.starts_with('#') {
+            continue;
+        }
+        if !suffix.starts_with('.') {
+            bail!(
+                "linha {}: sufixo inválido \"{}\" (esperado algo como .com.br)",
+                number + 1,
+                line.trim()
+            );
+        }
+        // Fora o ponto inicial, as mesmas regras do --suffix
+        let suffix =
+            normalize_suffix(&suffix).map_err(|e| anyhow!("linha {}: {}", number + 1, e))?;
+        if !suffixes.contains(&suffix) {
+            suffixes.push(suffix);
+        }
+    }
+    Ok(suffixes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_known_suffixes_are_unique_and_well_formed() {
+        let mut seen = HashSet::new();
+        for (suffix, _) in KNOWN_SUFFIXES {
+            assert!(seen.insert(suffix), "{} repetido", suffix);
+            let label = suffix
+                .strip_prefix('.')
+                .and_then(|s| s.strip_suffix(".br"))
+                .unwrap_or_else(|| panic!("{} fora do formato .xxx.br", suffix));
+            assert!(label.chars().all(|c| c.is_ascii_lowercase()), "{}", suffix);
+        }
+    }
+
+    #[test]
+    fn test_known_suffixes_by_category() {
+        assert_eq!(known_suffixes(&[]).len(), KNOWN_SUFFIXES.len());
+
+        let personal = known_suffixes(&[Personal]);
+        assert_eq!(
+            personal,
+            [".blog.br", ".flog.br", ".nom.br", ".vlog.br", ".wiki.br"]
+        );
+
+        let mixed = known_suffixes(&[Generic, Professional]);
+        assert_eq!(mixed[0], ".com.br");
+        assert!(mixed.contains(&".adv.br".to_string()));
+        assert!(!mixed.contains(&".rio.br".to_string()));
+    }
+
+    #[test]
+    fn test_restricted_suffixes_are_known() {
+        for (suffix, _) in RESTRICTED_SUFFIXES {
+            assert!(
+                KNOWN_SUFFIXES.iter().any(|(known, _)| known == suffix),
+                "{} não está em KNOWN_SUFFIXES",
+                suffix
+            );
+        }
+        assert_eq!(
+            restriction(".adv.br"),
+            Some("advogados com registro na OAB")
+        );
+        assert_eq!(restriction(".com.br"), None);
+    }
+
+    #[test]
+    fn test_normalize_suffix() {
+        for suffix in ["com.br", ".com.br", ".COM.BR", " .com.br "] {
+            assert_eq!(normalize_suffix(suffix).unwrap(), ".com.br", "{}", suffix);
+        }
+        assert_eq!(normalize_suffix(".br").unwrap(), ".br");
+        assert_eq!(normalize_suffix("br").unwrap(), ".br");
+
+        let error = normalize_suffix(".com").unwrap_err().to_string();
+        assert!(error.contains("não termina em .br"), "{}", error);
+        for invalid in [
+            "..br",
+            ".com..br",
+            ".com br",
+            ".lója.br",
+            ".-x.br",
+            ".x-.br",
+            "",
+        ] {
+            assert!(normalize_suffix(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_parse_suffix_list() {
+        let content = "# categorias de interesse\n.com.br\n\n  .NET.br \n.com.br\n.rio.br\n.br\n";
+        assert_eq!(
+            parse_suffix_list(content).unwrap(),
+            [".com.br", ".net.br", ".rio.br", ".br"]
+        );
+
+        for invalid in [
+            "com.br",
+            ".com",
+            "..br",
+            ".foo_bar.br",
+            ".-x.br",
+            ".x..br",
+            ".com br.br",
+        ] {
+            let content = format!(".com.br\n{}\n", invalid);
+            let error = parse_suffix_list(&content).unwrap_err().to_string();
+            assert!(error.starts_with("linha 2:"), "{}", error);
+        }
+    }
+}
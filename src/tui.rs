@@ -0,0 +1,289 @@
+//! Painel do modo `--tui`: progresso, domínios disponíveis recentes, distribuição
+//! de status e taxa de requisições, redesenhados em intervalo fixo.
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Gauge, List, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use registrobr_finder::stats::RecentRate;
+use registrobr_finder::{CancellationToken, DomainResult, StatusKind};
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Intervalo entre redesenhos do painel
+const REDRAW_INTERVAL: Duration = Duration::from_millis(250);
+/// Quantidade de domínios disponíveis recentes mantidos na lista
+const RECENT_AVAILABLE: usize = 100;
+/// Janela usada para medir a taxa atual de requisições
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// Barras do histograma, na ordem de exibição
+const KINDS: [(StatusKind, &str); 7] = [
+    (StatusKind::Available, "disponível"),
+    (StatusKind::Registered, "registrado"),
+    (StatusKind::InProcess, "em processo"),
+    (StatusKind::Release, "em liberação"),
+    (StatusKind::Unavailable, "indisponível"),
+    (StatusKind::Unknown, "desconhecido"),
+    (StatusKind::Error, "erro"),
+];
+
+/// Contadores alimentados pelo stream de resultados e lidos a cada redesenho
+#[derive(Debug)]
+pub struct DashboardState {
+    total: u64,
+    checked: u64,
+    counts: HashMap<StatusKind, u64>,
+    recent: VecDeque<String>,
+    started: Instant,
+    rate: RecentRate,
+}
+
+impl DashboardState {
+    fn new(total: u64) -> Self {
+        Self {
+            total,
+            checked: 0,
+            counts: HashMap::new(),
+            recent: VecDeque::new(),
+            started: Instant::now(),
+            rate: RecentRate::new(RATE_WINDOW),
+        }
+    }
+
+    pub fn record(&mut self, result: &DomainResult) {
+        self.checked += 1;
+        *self.counts.entry(result.kind()).or_default() += 1;
+        if result.available {
+            self.recent.push_front(result.display_name());
+            self.recent.truncate(RECENT_AVAILABLE);
+        }
+    }
+
+    fn count(&self, kind: StatusKind) -> u64 {
+        self.counts.get(&kind).copied().unwrap_or_default()
+    }
+
+    /// Requisições por segundo nos últimos `RATE_WINDOW`
+    fn current_rate(&mut self, now: Instant) -> f64 {
+        self.rate.record(now, self.checked);
+        self.rate.rate()
+    }
+
+    /// Tempo restante estimado pela taxa recente medida em
+    /// [`current_rate`](Self::current_rate), como na barra de progresso
+    fn eta(&self) -> Option<Duration> {
+        self.rate.eta(self.total.saturating_sub(self.checked))
+    }
+}
+
+pub type SharedDashboardState = Arc<Mutex<DashboardState>>;
+
+/// Painel em execução numa thread própria, dona do terminal
+pub struct Dashboard {
+    state: SharedDashboardState,
+    done: Arc<AtomicBool>,
+    handle: JoinHandle<io::Result<()>>,
+}
+
+impl Dashboard {
+    /// Entra na tela alternativa e passa a redesenhar o painel.
+    ///
+    /// Em modo raw o Ctrl+C chega como tecla e não como sinal: o primeiro marca
+    /// `interrupted`, como faria o handler do sinal, e o segundo restaura o terminal
+    /// e encerra o programa.
+    pub fn start(total: u64, interrupted: CancellationToken) -> io::Result<Self> {
+        let terminal = ratatui::try_init()?;
+        let state = Arc::new(Mutex::new(DashboardState::new(total)));
+        let done = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let state = state.clone();
+            let done = done.clone();
+            thread::spawn(move || {
+                let result = run(terminal, &state, &done, &interrupted);
+                ratatui::restore();
+                result
+            })
+        };
+
+        Ok(Self {
+            state,
+            done,
+            handle,
+        })
+    }
+
+    pub fn state(&self) -> SharedDashboardState {
+        self.state.clone()
+    }
+
+    /// Desenha o estado final e restaura o terminal
+    pub fn finish(self) -> io::Result<()> {
+        self.done.store(true, Ordering::Relaxed);
+        self.handle
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("o painel foi encerrado inesperadamente")))
+    }
+}
+
+fn run(
+    mut terminal: DefaultTerminal,
+    state: &Mutex<DashboardState>,
+    done: &AtomicBool,
+    interrupted: &CancellationToken,
+) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| {
+            draw(
+                frame,
+                &mut state.lock().unwrap(),
+                interrupted.is_cancelled(),
+            )
+        })?;
+        if done.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        if event::poll(REDRAW_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                let ctrl_c = key.kind == KeyEventKind::Press
+                    && key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(KeyModifiers::CONTROL);
+                if ctrl_c {
+                    if interrupted.is_cancelled() {
+                        ratatui::restore();
+                        std::process::exit(130);
+                    }
+                    interrupted.cancel();
+                }
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, state: &mut DashboardState, interrupted: bool) {
+    let now = Instant::now();
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(8),
+        Constraint::Length(3),
+    ])
+    .areas(frame.area());
+    let [recent, histogram] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(body);
+
+    let ratio = if state.total == 0 {
+        1.0
+    } else {
+        (state.checked as f64 / state.total as f64).min(1.0)
+    };
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::bordered().title(" Progresso "))
+            .gauge_style(Style::new().fg(Color::Cyan))
+            .ratio(ratio)
+            .label(format!(
+                "{}/{} ({:.1}%)",
+                state.checked,
+                state.total,
+                ratio * 100.0
+            )),
+        header,
+    );
+
+    frame.render_widget(
+        List::new(state.recent.iter().map(String::as_str)).block(Block::bordered().title(format!(
+            " Disponíveis ({}) ",
+            state.count(StatusKind::Available)
+        ))),
+        recent,
+    );
+
+    let bars: Vec<Bar> = KINDS
+        .iter()
+        .map(|&(kind, label)| {
+            let count = state.count(kind);
+            Bar::default()
+                .label(Line::from(label))
+                .value(count)
+                .text_value(count.to_string())
+        })
+        .collect();
+    frame.render_widget(
+        BarChart::default()
+            .block(Block::bordered().title(" Status "))
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(0)
+            .bar_style(Style::new().fg(Color::Green))
+            .data(BarGroup::default().bars(&bars)),
+        histogram,
+    );
+
+    let errors = state.count(StatusKind::Error);
+    let error_rate = if state.checked == 0 {
+        0.0
+    } else {
+        errors as f64 * 100.0 / state.checked as f64
+    };
+    let mut status = format!(
+        "{:.1} req/s | erros: {} ({:.1}%) | decorrido: {} | restante: {}",
+        state.current_rate(now),
+        errors,
+        error_rate,
+        format_duration(now.duration_since(state.started)),
+        state.eta().map_or("-".to_string(), format_duration)
+    );
+    if interrupted {
+        status.push_str(" | interrompendo (Ctrl+C de novo para sair)");
+    }
+    frame.render_widget(Paragraph::new(status).block(Block::bordered()), footer);
+}
+
+/// Duração no formato HH:MM:SS
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dashboard_state_counts_and_recent() {
+        let mut state = DashboardState::new(4);
+        let started = Instant::now();
+        assert_eq!(state.current_rate(started), 0.0);
+        state.record(&DomainResult {
+            domain: "aa.com.br".to_string(),
+            available: true,
+            ..Default::default()
+        });
+        state.record(&DomainResult::failed("ab.com.br".to_string(), "HTTP 500"));
+        state.record(&DomainResult {
+            domain: "ac.com.br".to_string(),
+            available: true,
+            ..Default::default()
+        });
+
+        assert_eq!(state.checked, 3);
+        assert_eq!(state.count(StatusKind::Available), 2);
+        assert_eq!(state.count(StatusKind::Error), 1);
+        assert_eq!(state.recent, ["ac.com.br", "aa.com.br"]);
+        assert_eq!(state.current_rate(started + Duration::from_secs(1)), 3.0);
+        assert_eq!(state.eta(), Some(Duration::from_secs_f64(1.0 / 3.0)));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(3725)), "01:02:05");
+    }
+}
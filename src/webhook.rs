@@ -0,0 +1,115 @@
+use reqwest::Client;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{self, Duration, Instant};
+
+use crate::DomainResult;
+
+/// Janela padrão para agrupar vários domínios encontrados em um único POST
+pub const DEFAULT_BATCH_WINDOW: Duration = Duration::from_secs(2);
+
+/// Corpo enviado ao webhook para cada domínio disponível
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Notification {
+    pub domain: String,
+    pub status: String,
+}
+
+impl From<&DomainResult> for Notification {
+    fn from(result: &DomainResult) -> Self {
+        Self {
+            domain: result.domain.clone(),
+            status: result
+                .status
+                .clone()
+                .unwrap_or_else(|| "disponível".to_string()),
+        }
+    }
+}
+
+/// Corpo de um POST: o objeto quando houve um só domínio na janela, ou um array
+fn body(batch: &[Notification]) -> serde_json::Value {
+    match batch {
+        [single] => serde_json::json!(single),
+        _ => serde_json::json!(batch),
+    }
+}
+
+/// Envia notificações a um webhook em segundo plano. Domínios encontrados dentro de
+/// uma mesma janela são agrupados em um único POST; falhas são repassadas a
+/// `on_error` sem interromper a varredura.
+#[derive(Debug)]
+pub struct WebhookNotifier {
+    sender: mpsc::UnboundedSender<Notification>,
+    task: JoinHandle<()>,
+}
+
+impl WebhookNotifier {
+    pub fn spawn(
+        client: Client,
+        url: String,
+        window: Duration,
+        on_error: impl Fn(String) + Send + 'static,
+    ) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+
+        let task = tokio::spawn(async move {
+            while let Some(first) = receiver.recv().await {
+                let mut batch = vec![first];
+                let deadline = Instant::now() + window;
+                while let Ok(Some(next)) = time::timeout_at(deadline, receiver.recv()).await {
+                    batch.push(next);
+                }
+
+                let response = client.post(&url).json(&body(&batch)).send().await;
+                if let Err(e) = response.and_then(|response| response.error_for_status()) {
+                    on_error(format!(
+                        "falha ao notificar {} domínio(s) no webhook: {}",
+                        batch.len(),
+                        e
+                    ));
+                }
+            }
+        });
+
+        Self { sender, task }
+    }
+
+    /// Agenda a notificação de um domínio disponível
+    pub fn notify(&self, result: &DomainResult) {
+        // O envio só falha se a tarefa já terminou, o que não acontece antes de `finish`
+        let _ = self.sender.send(result.into());
+    }
+
+    /// Envia o que ainda estiver pendente e aguarda a tarefa terminar
+    pub async fn finish(self) {
+        drop(self.sender);
+        let _ = self.task.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_is_object_for_single_find_and_array_for_batch() {
+        let notification = |domain: &str| Notification {
+            domain: domain.to_string(),
+            status: "disponível".to_string(),
+        };
+
+        assert_eq!(
+            body(&[notification("loja.com.br")]),
+            serde_json::json!({"domain": "loja.com.br", "status": "disponível"})
+        );
+        assert_eq!(
+            body(&[notification("loja.com.br"), notification("teste.com.br")]),
+            serde_json::json!([
+                {"domain": "loja.com.br", "status": "disponível"},
+                {"domain": "teste.com.br", "status": "disponível"}
+            ])
+        );
+    }
+}